@@ -0,0 +1,321 @@
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// How close θ/π has to be to a fraction before it is shown as one.
+const PI_FRACTION_TOLERANCE: f32 = 1e-3;
+/// The largest denominator tried when writing θ as a fraction of π.
+const MAX_PI_DENOMINATOR: i32 = 12;
+
+/// The unit θ is shown and entered in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AngleUnit {
+    Radians,
+    Degrees,
+    Gradians,
+    Turns,
+    /// Radians written as a multiple of π, e.g. `3π/4`.
+    #[serde(rename = "pi")]
+    PiFraction,
+}
+
+impl Default for AngleUnit {
+    fn default() -> Self {
+        AngleUnit::Degrees
+    }
+}
+
+impl AngleUnit {
+    pub const ALL: [AngleUnit; 5] = [
+        AngleUnit::Radians,
+        AngleUnit::Degrees,
+        AngleUnit::Gradians,
+        AngleUnit::Turns,
+        AngleUnit::PiFraction,
+    ];
+
+    /// The unit after this one, wrapping around.
+    pub fn next(self) -> Self {
+        let index = AngleUnit::ALL
+            .iter()
+            .position(|unit| *unit == self)
+            .unwrap();
+        AngleUnit::ALL[(index + 1) % AngleUnit::ALL.len()]
+    }
+
+    /// The unit called `name`, as written by [`AngleUnit::name`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        AngleUnit::ALL
+            .iter()
+            .copied()
+            .find(|unit| unit.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AngleUnit::Radians => "radians",
+            AngleUnit::Degrees => "degrees",
+            AngleUnit::Gradians => "gradians",
+            AngleUnit::Turns => "turns",
+            AngleUnit::PiFraction => "pi",
+        }
+    }
+
+    /// How many radians one of this unit is.
+    fn radians_per_unit(self) -> f32 {
+        match self {
+            AngleUnit::Radians => 1.,
+            AngleUnit::Degrees => PI / 180.,
+            AngleUnit::Gradians => PI / 200.,
+            AngleUnit::Turns => 2. * PI,
+            AngleUnit::PiFraction => PI,
+        }
+    }
+
+    /// Converts θ in radians to this unit.
+    pub fn from_radians(self, theta: f32) -> f32 {
+        theta / self.radians_per_unit()
+    }
+
+    /// Converts a value in this unit to radians.
+    pub fn to_radians(self, value: f32) -> f32 {
+        value * self.radians_per_unit()
+    }
+
+    /// The resolution mouse input is snapped to, in radians.
+    pub fn step(self) -> f32 {
+        match self {
+            AngleUnit::Radians => 0.01,
+            AngleUnit::Degrees => self.to_radians(1.),
+            AngleUnit::Gradians => self.to_radians(1.),
+            AngleUnit::Turns => self.to_radians(0.01),
+            AngleUnit::PiFraction => PI / 12.,
+        }
+    }
+
+    /// Rounds θ to the nearest [`AngleUnit::step`].
+    pub fn snap(self, theta: f32) -> f32 {
+        let step = self.step();
+        (theta / step).round() * step
+    }
+
+    /// Formats θ, given in radians, in this unit.
+    pub fn format(self, theta: f32) -> String {
+        let value = self.from_radians(theta);
+        match self {
+            AngleUnit::Radians => format!("{:.3} rad", value),
+            AngleUnit::Degrees => format!("{:.1}°", value),
+            AngleUnit::Gradians => format!("{:.1} gon", value),
+            AngleUnit::Turns => format!("{:.3} turn", value),
+            AngleUnit::PiFraction => format_pi_fraction(value),
+        }
+    }
+
+    /// A short label for θ, for the ticks around the circle.
+    pub fn tick_label(self, theta: f32) -> String {
+        let value = self.from_radians(theta);
+        match self {
+            AngleUnit::Radians => trim_decimals(value, 2),
+            AngleUnit::Degrees => format!("{}°", trim_decimals(value, 1)),
+            AngleUnit::Gradians => trim_decimals(value, 1),
+            AngleUnit::Turns => trim_decimals(value, 3),
+            AngleUnit::PiFraction => format_pi_fraction(value),
+        }
+    }
+}
+
+/// `value` with at most `decimals` decimals, leaving off trailing zeros.
+fn trim_decimals(value: f32, decimals: usize) -> String {
+    let formatted = format!("{:.*}", decimals, value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "-0" => "0".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+/// Moves θ by `steps` whole steps, landing on a multiple of `step` and
+/// wrapping into `[0, 2π)`.
+pub fn step_angle(theta: f32, step: f32, steps: f32) -> f32 {
+    (((theta / step).round() + steps) * step).rem_euclid(2. * PI)
+}
+
+/// The angles θ snaps to while dragging in snapping mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SnapAngles {
+    /// Every multiple of the given angle, in radians.
+    Multiples(f32),
+    /// The special angles: multiples of 30° and of 45°.
+    Special,
+}
+
+/// Magnetic snapping of dragged angles.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Snapping {
+    pub enabled: bool,
+    pub angles: SnapAngles,
+    /// How close, in radians, θ has to get to an angle to be pulled onto it.
+    pub threshold: f32,
+}
+
+impl Default for Snapping {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            angles: SnapAngles::Multiples(PI / 12.),
+            threshold: 4f32.to_radians(),
+        }
+    }
+}
+
+impl Snapping {
+    /// The angle θ snaps to, if it is close enough to one.
+    ///
+    /// This ignores `enabled`, callers decide whether snapping applies.
+    pub fn snap(&self, theta: f32) -> Option<f32> {
+        let nearest = |step: f32| (theta / step).round() * step;
+        let target = match self.angles {
+            SnapAngles::Multiples(step) => nearest(step),
+            SnapAngles::Special => {
+                let (by_30, by_45) = (nearest(PI / 6.), nearest(PI / 4.));
+                if (theta - by_30).abs() < (theta - by_45).abs() {
+                    by_30
+                } else {
+                    by_45
+                }
+            }
+        };
+        if (theta - target).abs() <= self.threshold {
+            Some(target)
+        } else {
+            None
+        }
+    }
+
+    /// Whether snapping is on and θ sits exactly on one of its angles.
+    pub fn is_snapped(&self, theta: f32) -> bool {
+        self.enabled
+            && self
+                .snap(theta)
+                .map_or(false, |target| (theta - target).abs() < 1e-5)
+    }
+}
+
+/// Writes `value`π as a fraction like `3π/4`, or as a decimal multiple of π if
+/// it isn't close to a fraction with a small denominator.
+fn format_pi_fraction(value: f32) -> String {
+    for denominator in 1..=MAX_PI_DENOMINATOR {
+        let numerator = value * denominator as f32;
+        if (numerator - numerator.round()).abs() > PI_FRACTION_TOLERANCE * denominator as f32 {
+            continue;
+        }
+        let numerator = numerator.round() as i32;
+        let coefficient = match numerator {
+            0 => return "0".to_string(),
+            1 => "".to_string(),
+            -1 => "-".to_string(),
+            n => n.to_string(),
+        };
+        return if denominator == 1 {
+            format!("{}π", coefficient)
+        } else {
+            format!("{}π/{}", coefficient, denominator)
+        };
+    }
+    format!("{:.3}π", value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_4;
+
+    #[test]
+    fn converts_between_units() {
+        assert!((AngleUnit::Degrees.from_radians(PI) - 180.).abs() < 1e-4);
+        assert!((AngleUnit::Gradians.from_radians(PI) - 200.).abs() < 1e-4);
+        assert!((AngleUnit::Turns.from_radians(PI) - 0.5).abs() < 1e-6);
+        assert!((AngleUnit::Turns.to_radians(0.25) - PI / 2.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn formats_each_unit() {
+        assert_eq!(AngleUnit::Radians.format(FRAC_PI_4), "0.785 rad");
+        assert_eq!(AngleUnit::Degrees.format(FRAC_PI_4), "45.0°");
+        assert_eq!(AngleUnit::Gradians.format(FRAC_PI_4), "50.0 gon");
+        assert_eq!(AngleUnit::Turns.format(FRAC_PI_4), "0.125 turn");
+        assert_eq!(AngleUnit::PiFraction.format(3. * FRAC_PI_4), "3π/4");
+    }
+
+    #[test]
+    fn tick_labels_are_short() {
+        let step = PI / 12.;
+        assert_eq!(AngleUnit::Radians.tick_label(step), "0.26");
+        assert_eq!(AngleUnit::Degrees.tick_label(3. * step), "45°");
+        assert_eq!(AngleUnit::Gradians.tick_label(step), "16.7");
+        assert_eq!(AngleUnit::Turns.tick_label(6. * step), "0.25");
+        assert_eq!(AngleUnit::PiFraction.tick_label(step), "π/12");
+        assert_eq!(AngleUnit::Degrees.tick_label(0.), "0°");
+    }
+
+    #[test]
+    fn formats_fractions_of_pi() {
+        let format = |theta| AngleUnit::PiFraction.format(theta);
+        assert_eq!(format(0.), "0");
+        assert_eq!(format(PI), "π");
+        assert_eq!(format(2. * PI), "2π");
+        assert_eq!(format(-PI / 6.), "-π/6");
+        assert_eq!(format(7. * PI / 12.), "7π/12");
+        assert_eq!(format(1.), "0.318π");
+    }
+
+    #[test]
+    fn snaps_to_the_unit_step() {
+        let snapped = AngleUnit::Degrees.snap(0.7855);
+        assert!((AngleUnit::Degrees.from_radians(snapped) - 45.).abs() < 1e-3);
+        assert_eq!(
+            AngleUnit::PiFraction.format(AngleUnit::PiFraction.snap(0.8)),
+            "π/4"
+        );
+    }
+
+    #[test]
+    fn steps_land_on_the_grid() {
+        let step = PI / 12.;
+        assert!((step_angle(FRAC_PI_4 + 0.01, step, 1.) - PI / 3.).abs() < 1e-5);
+        assert!((step_angle(0., step, -1.) - 23. * step).abs() < 1e-5);
+    }
+
+    #[test]
+    fn snapping_is_magnetic() {
+        let snapping = Snapping {
+            enabled: true,
+            ..Snapping::default()
+        };
+        assert_eq!(snapping.snap(FRAC_PI_4 + 0.03), Some(FRAC_PI_4));
+        assert_eq!(snapping.snap(FRAC_PI_4 + 0.1), None);
+        assert!(snapping.is_snapped(PI / 12.));
+        assert!(!snapping.is_snapped(PI / 12. + 0.01));
+    }
+
+    #[test]
+    fn special_snapping_skips_15_degrees() {
+        let snapping = Snapping {
+            enabled: true,
+            angles: SnapAngles::Special,
+            threshold: 0.2,
+        };
+        assert_eq!(snapping.snap(PI / 12.), None);
+        assert_eq!(snapping.snap(PI / 6. - 0.1), Some(PI / 6.));
+        assert_eq!(snapping.snap(FRAC_PI_4 + 0.1), Some(FRAC_PI_4));
+    }
+
+    #[test]
+    fn next_cycles_through_every_unit() {
+        let mut unit = AngleUnit::Radians;
+        for _ in 0..AngleUnit::ALL.len() {
+            unit = unit.next();
+        }
+        assert_eq!(unit, AngleUnit::Radians);
+    }
+}
@@ -0,0 +1,349 @@
+use crate::angle::AngleUnit;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{FRAC_PI_2, PI};
+
+/// The slowest an easing curve slows θ down to, as a fraction of the speed,
+/// so that the animation never stalls.
+const MIN_EASING_FACTOR: f32 = 0.1;
+
+/// Which way θ sweeps round the circle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Anticlockwise, θ increasing.
+    Forward,
+    /// Clockwise, θ decreasing.
+    Backward,
+}
+
+impl Default for Direction {
+    fn default() -> Self {
+        Direction::Forward
+    }
+}
+
+impl Direction {
+    pub fn reversed(self) -> Self {
+        match self {
+            Direction::Forward => Direction::Backward,
+            Direction::Backward => Direction::Forward,
+        }
+    }
+
+    pub fn sign(self) -> f32 {
+        match self {
+            Direction::Forward => 1.,
+            Direction::Backward => -1.,
+        }
+    }
+}
+
+/// The part of the circle θ is animated over. In a config file it's either
+/// `full` or e.g. `ping_pong(start: -1.5708, end: 1.5708)`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sweep {
+    /// Round and round the whole circle.
+    Full,
+    /// Back and forth between `start` and `end`, where `start < end`.
+    PingPong { start: f32, end: f32 },
+}
+
+impl Default for Sweep {
+    fn default() -> Self {
+        Sweep::Full
+    }
+}
+
+impl Sweep {
+    /// The sweeps the on-screen controls cycle through.
+    pub const PRESETS: [Sweep; 3] = [
+        Sweep::Full,
+        // one branch of tan
+        Sweep::PingPong {
+            start: -FRAC_PI_2,
+            end: FRAC_PI_2,
+        },
+        // one branch of cot
+        Sweep::PingPong { start: 0., end: PI },
+    ];
+
+    /// Back and forth between `from` and `to`, in radians and either order.
+    /// Ends a whole turn or more apart make the full circle.
+    pub fn between(from: f32, to: f32) -> Result<Self, String> {
+        if !from.is_finite() || !to.is_finite() || from == to {
+            return Err(format!("can't sweep from {} to {}", from, to));
+        }
+        if (to - from).abs() >= 2. * PI {
+            Ok(Sweep::Full)
+        } else {
+            Ok(Sweep::PingPong {
+                start: from.min(to),
+                end: from.max(to),
+            })
+        }
+    }
+
+    /// The same sweep if it is one [`Sweep::between`] would make, or else an
+    /// error saying why not.
+    pub fn check(self) -> Result<Self, String> {
+        match self {
+            Sweep::Full => Ok(self),
+            Sweep::PingPong { start, end } if start < end => Sweep::between(start, end),
+            Sweep::PingPong { start, end } => Err(format!(
+                "a sweep has to start before it ends, not at {} and {}",
+                start, end
+            )),
+        }
+    }
+
+    /// The preset after this one, wrapping around. Custom sweeps go back to
+    /// the full circle.
+    pub fn next(self) -> Self {
+        match Sweep::PRESETS.iter().position(|sweep| *sweep == self) {
+            Some(index) => Sweep::PRESETS[(index + 1) % Sweep::PRESETS.len()],
+            None => Sweep::Full,
+        }
+    }
+
+    pub fn name(self) -> String {
+        match self {
+            Sweep::Full => "full circle".into(),
+            Sweep::PingPong { start, end } => format!(
+                "{} to {}",
+                AngleUnit::PiFraction.format(start),
+                AngleUnit::PiFraction.format(end)
+            ),
+        }
+    }
+
+    /// θ moved into the sweep: the same angle if it is already in there,
+    /// otherwise whichever end is closer.
+    pub fn contain(self, theta: f32) -> f32 {
+        match self {
+            Sweep::Full => theta.rem_euclid(2. * PI),
+            Sweep::PingPong { start, end } => {
+                let theta = start + (theta - start).rem_euclid(2. * PI);
+                if theta <= end {
+                    theta
+                } else if theta - end < start + 2. * PI - theta {
+                    end
+                } else {
+                    start
+                }
+            }
+        }
+    }
+}
+
+/// Changes how fast θ moves depending on where it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    /// Slows down as tan or cot heads off to infinity, i.e. near the axes.
+    SlowNearAsymptotes,
+    /// Slows down at the ends of a ping-pong sweep.
+    SlowAtEnds,
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::Linear
+    }
+}
+
+impl Easing {
+    pub const ALL: [Easing; 3] = [
+        Easing::Linear,
+        Easing::SlowNearAsymptotes,
+        Easing::SlowAtEnds,
+    ];
+
+    /// The easing after this one, wrapping around.
+    pub fn next(self) -> Self {
+        let index = Easing::ALL
+            .iter()
+            .position(|easing| *easing == self)
+            .unwrap();
+        Easing::ALL[(index + 1) % Easing::ALL.len()]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Easing::Linear => "linear",
+            Easing::SlowNearAsymptotes => "slow near asymptotes",
+            Easing::SlowAtEnds => "slow at ends",
+        }
+    }
+
+    /// How much of the full speed θ moves at, between `MIN_EASING_FACTOR`
+    /// and 1.
+    pub fn factor(self, theta: f32, sweep: Sweep) -> f32 {
+        let factor = match (self, sweep) {
+            (Easing::Linear, _) | (Easing::SlowAtEnds, Sweep::Full) => 1.,
+            // |sin 2θ| is zero on every asymptote of tan and cot, the square
+            // root keeps it from crawling everywhere in between
+            (Easing::SlowNearAsymptotes, _) => (2. * theta).sin().abs().sqrt(),
+            (Easing::SlowAtEnds, Sweep::PingPong { start, end }) => {
+                let t = ((theta - start) / (end - start)).clamp(0., 1.);
+                (PI * t).sin().sqrt()
+            }
+        };
+        factor.max(MIN_EASING_FACTOR)
+    }
+}
+
+/// How θ is animated while the visualization isn't paused.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationSettings {
+    /// Angular speed in radians per second.
+    pub speed: f32,
+    pub direction: Direction,
+    pub sweep: Sweep,
+    pub easing: Easing,
+}
+
+impl AnimationSettings {
+    pub const MIN_SPEED: f32 = 0.05;
+    pub const MAX_SPEED: f32 = 8.;
+    /// How much faster or slower each step of the speed controls makes it.
+    pub const SPEED_STEP: f32 = 1.25;
+
+    /// Scales the speed by `factor`, keeping it within sensible bounds.
+    pub fn scale_speed(&mut self, factor: f32) {
+        self.speed = (self.speed * factor).clamp(Self::MIN_SPEED, Self::MAX_SPEED);
+    }
+
+    /// θ after `delta_seconds` of animation. Bouncing off either end of a
+    /// ping-pong sweep reverses the direction.
+    pub fn advance(&mut self, theta: f32, delta_seconds: f32) -> f32 {
+        let theta = self.sweep.contain(theta);
+        let step = delta_seconds
+            * self.speed
+            * self.easing.factor(theta, self.sweep)
+            * self.direction.sign();
+        match self.sweep {
+            Sweep::Full => (theta + step).rem_euclid(2. * PI),
+            Sweep::PingPong { start, end } => {
+                let theta = theta + step;
+                if theta > end {
+                    self.direction = Direction::Backward;
+                    (2. * end - theta).max(start)
+                } else if theta < start {
+                    self.direction = Direction::Forward;
+                    (2. * start - theta).min(end)
+                } else {
+                    theta
+                }
+            }
+        }
+    }
+}
+
+impl Default for AnimationSettings {
+    fn default() -> Self {
+        Self {
+            speed: 0.5,
+            direction: Direction::Forward,
+            sweep: Sweep::Full,
+            easing: Easing::Linear,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speed_stays_in_bounds() {
+        let mut settings = AnimationSettings::default();
+        for _ in 0..100 {
+            settings.scale_speed(2.);
+        }
+        assert_eq!(settings.speed, AnimationSettings::MAX_SPEED);
+        for _ in 0..100 {
+            settings.scale_speed(0.5);
+        }
+        assert_eq!(settings.speed, AnimationSettings::MIN_SPEED);
+    }
+
+    #[test]
+    fn full_sweep_wraps() {
+        let mut settings = AnimationSettings::default();
+        let theta = settings.advance(2. * PI - 0.1, 1.);
+        assert!((theta - 0.4).abs() < 1e-4);
+
+        settings.direction = Direction::Backward;
+        let theta = settings.advance(0.1, 1.);
+        assert!((theta - (2. * PI - 0.4)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn ping_pong_bounces_off_the_ends() {
+        let mut settings = AnimationSettings {
+            speed: 1.,
+            sweep: Sweep::PRESETS[1],
+            ..Default::default()
+        };
+        let theta = settings.advance(FRAC_PI_2 - 0.25, 0.5);
+        assert!((theta - (FRAC_PI_2 - 0.25)).abs() < 1e-4);
+        assert_eq!(settings.direction, Direction::Backward);
+
+        let theta = settings.advance(-FRAC_PI_2 + 0.25, 0.5);
+        assert!((theta - (-FRAC_PI_2 + 0.25)).abs() < 1e-4);
+        assert_eq!(settings.direction, Direction::Forward);
+    }
+
+    #[test]
+    fn angles_outside_the_sweep_go_to_the_nearer_end() {
+        let sweep = Sweep::PingPong {
+            start: -FRAC_PI_2,
+            end: FRAC_PI_2,
+        };
+        assert!((sweep.contain(2. * PI - 0.5) + 0.5).abs() < 1e-4);
+        assert_eq!(sweep.contain(FRAC_PI_2 + 0.5), FRAC_PI_2);
+        assert_eq!(sweep.contain(3. * FRAC_PI_2 - 0.5), -FRAC_PI_2);
+        assert_eq!(Sweep::Full.contain(-0.5), 2. * PI - 0.5);
+    }
+
+    #[test]
+    fn easing_slows_near_asymptotes() {
+        let easing = Easing::SlowNearAsymptotes;
+        let sweep = Sweep::Full;
+        assert_eq!(easing.factor(FRAC_PI_2, sweep), MIN_EASING_FACTOR);
+        assert_eq!(easing.factor(PI / 4., sweep), 1.);
+        assert!(easing.factor(FRAC_PI_2 - 0.05, sweep) < easing.factor(1., sweep));
+        assert_eq!(Easing::SlowAtEnds.factor(0.3, sweep), 1.);
+    }
+
+    #[test]
+    fn presets_cycle() {
+        assert_eq!(Sweep::Full.next(), Sweep::PRESETS[1]);
+        assert_eq!(Sweep::PRESETS[2].next(), Sweep::Full);
+        assert_eq!(Sweep::PRESETS[1].name(), "-π/2 to π/2");
+        let custom = Sweep::PingPong { start: 0., end: 1. };
+        assert_eq!(custom.next(), Sweep::Full);
+    }
+
+    #[test]
+    fn sweeps_between_any_two_angles() {
+        assert_eq!(
+            Sweep::between(2., -1.),
+            Ok(Sweep::PingPong {
+                start: -1.,
+                end: 2.
+            })
+        );
+        assert_eq!(Sweep::between(0., 7.), Ok(Sweep::Full));
+        assert!(Sweep::between(1., 1.).is_err());
+        assert!(Sweep::between(0., f32::NAN).is_err());
+        assert!(Sweep::PingPong { start: 1., end: 0. }.check().is_err());
+        assert_eq!(Sweep::PRESETS[2].check(), Ok(Sweep::PRESETS[2]));
+    }
+
+    #[test]
+    fn reversing_twice_is_a_no_op() {
+        assert_eq!(Direction::Forward.reversed().sign(), -1.);
+        assert_eq!(Direction::Forward.reversed().reversed(), Direction::Forward);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::geometry::{Bounds, Point, Segment};
+use std::f32::consts::PI;
+
+/// How many points make up a full turn of the angle arc.
+const ARC_SAMPLES: usize = 64;
+/// The space left between a label and its segment, in pixels.
+const LABEL_GAP: f32 = 4.;
+/// Where along its segment a label is tried, in order of preference.
+const ALONG: [f32; 5] = [0.5, 0.3, 0.7, 0.15, 0.85];
+
+/// The wedge from the positive x axis round to θ, as a closed polygon
+/// starting at the origin. Negative θ goes clockwise.
+pub fn angle_arc(theta: f32, radius: f32) -> Vec<Point> {
+    sector(0., theta, radius)
+}
+
+/// The wedge from `start` round to `end`, as a closed polygon starting at the
+/// origin. It goes clockwise if `end` is less than `start`.
+pub fn sector(start: f32, end: f32, radius: f32) -> Vec<Point> {
+    let sweep = (end - start).clamp(-2. * PI, 2. * PI);
+    let samples = ((sweep.abs() / (2. * PI) * ARC_SAMPLES as f32).ceil() as usize).max(1);
+    let mut points = vec![Point::ORIGIN];
+    for i in 0..=samples {
+        let angle = start + sweep * i as f32 / samples as f32;
+        points.push(Point::new(radius * angle.cos(), radius * angle.sin()));
+    }
+    points
+}
+
+/// Halfway round the angle arc, `distance` from the origin.
+pub fn arc_label_position(theta: f32, distance: f32) -> Point {
+    let half = theta.clamp(-2. * PI, 2. * PI) / 2.;
+    Point::new(distance * half.cos(), distance * half.sin())
+}
+
+/// A label of the given size that belongs next to `segment`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentLabel {
+    pub segment: Segment,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl SegmentLabel {
+    fn bounds(&self, centre: Point) -> Bounds {
+        Bounds::centered(centre, self.width, self.height)
+    }
+
+    /// Where the label could go, best first: beside the middle of the segment,
+    /// then further along it either way, on either side, then further out.
+    fn candidates(&self) -> impl Iterator<Item = Point> + '_ {
+        let length = self.segment.length();
+        let (nx, ny) = if length > 0. {
+            (
+                -(self.segment.end.y - self.segment.start.y) / length,
+                (self.segment.end.x - self.segment.start.x) / length,
+            )
+        } else {
+            (0., 1.)
+        };
+        // far enough out along the normal for the whole box to clear the line
+        let clearance = (nx.abs() * self.width + ny.abs() * self.height) / 2. + LABEL_GAP;
+        (0..3).flat_map(move |ring| {
+            let distance = clearance + ring as f32 * self.height;
+            ALONG.iter().flat_map(move |t| {
+                let at = self.segment.lerp(*t);
+                [1f32, -1.]
+                    .iter()
+                    .map(move |side| {
+                        Point::new(at.x + side * distance * nx, at.y + side * distance * ny)
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+    }
+}
+
+/// Picks a centre for each label, in order, so that none of them overlap each
+/// other or any of the `obstacles`. A label that can't be fitted anywhere
+/// goes in its first choice.
+pub fn place_labels(labels: &[SegmentLabel], obstacles: &[Bounds]) -> Vec<Point> {
+    let mut taken = obstacles.to_vec();
+    let mut centres = Vec::with_capacity(labels.len());
+    for label in labels {
+        let centre = label
+            .candidates()
+            .find(|centre| {
+                let bounds = label.bounds(*centre);
+                !taken.iter().any(|other| other.overlaps(&bounds))
+            })
+            .or_else(|| label.candidates().next())
+            .unwrap();
+        taken.push(label.bounds(centre));
+        centres.push(centre);
+    }
+    centres
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn arc_runs_from_the_x_axis_to_theta() {
+        let arc = angle_arc(FRAC_PI_2, 10.);
+        assert_eq!(arc[0], Point::ORIGIN);
+        assert_eq!(arc[1], Point::new(10., 0.));
+        let last = arc[arc.len() - 1];
+        assert!(last.x.abs() < 1e-4 && (last.y - 10.).abs() < 1e-4);
+        assert_eq!(arc.len(), 2 + ARC_SAMPLES / 4);
+
+        let clockwise = angle_arc(-FRAC_PI_2, 10.);
+        assert!(clockwise[clockwise.len() - 1].y < 0.);
+    }
+
+    #[test]
+    fn sector_runs_between_any_two_angles() {
+        let right_half = sector(-FRAC_PI_2, FRAC_PI_2, 10.);
+        assert_eq!(right_half[0], Point::ORIGIN);
+        assert!((right_half[1].y + 10.).abs() < 1e-4);
+        assert!((right_half[right_half.len() - 1].y - 10.).abs() < 1e-4);
+        assert!((right_half[right_half.len() / 2].x - 10.).abs() < 1e-4);
+    }
+
+    #[test]
+    fn theta_label_bisects_the_angle() {
+        let position = arc_label_position(FRAC_PI_2, 10.);
+        assert!((position.x - position.y).abs() < 1e-4);
+    }
+
+    #[test]
+    fn labels_sit_beside_their_segment() {
+        let label = SegmentLabel {
+            segment: Segment::new(Point::ORIGIN, Point::new(100., 0.)),
+            width: 40.,
+            height: 16.,
+        };
+        let centres = place_labels(&[label], &[]);
+        assert_eq!(centres[0], Point::new(50., 8. + LABEL_GAP));
+    }
+
+    #[test]
+    fn labels_do_not_overlap() {
+        let segment = Segment::new(Point::ORIGIN, Point::new(100., 0.));
+        let label = SegmentLabel {
+            segment,
+            width: 40.,
+            height: 16.,
+        };
+        let centres = place_labels(&[label, label, label], &[]);
+        for (i, a) in centres.iter().enumerate() {
+            for b in centres.iter().skip(i + 1) {
+                assert!(!label.bounds(*a).overlaps(&label.bounds(*b)));
+            }
+        }
+        // the second label goes on the other side of the line
+        assert_eq!(centres[1], Point::new(50., -8. - LABEL_GAP));
+    }
+
+    #[test]
+    fn labels_avoid_obstacles() {
+        let label = SegmentLabel {
+            segment: Segment::new(Point::ORIGIN, Point::new(100., 0.)),
+            width: 40.,
+            height: 16.,
+        };
+        let obstacle = Bounds::centered(Point::new(50., 12.), 60., 10.);
+        let centre = place_labels(&[label], &[obstacle])[0];
+        assert!(!label.bounds(centre).overlaps(&obstacle));
+    }
+}
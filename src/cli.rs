@@ -0,0 +1,453 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use trig_geometry::plugin::ImageFormat;
+use trig_geometry::{AngleUnit, Config, Function, Recording, Sweep, Table, TableFormat, Theme};
+
+pub const USAGE: &str = "\
+usage: trig-visualization [options]
+
+Starting state, also used for snapshots, recordings and tables:
+  --config FILE     read the options below from a RON file, the command line
+                    overrides it
+  --theta RADIANS   θ to start at (default 0)
+  --paused          start with the animation paused
+  --speed N         how fast θ turns, in radians per second (default 0.5)
+  --sweep FROM,TO   turn θ back and forth between two angles in radians, or
+                    round the full circle (default full)
+  --snapping        snap dragged angles to multiples of 15°
+  --size WxH        size of the window or image in pixels (default 1200x720)
+  --fullscreen      open the window fullscreen
+  --functions LIST  the functions to show, e.g. cos,sin,tan (default the six
+                    basic ones); also versin, coversin, exsec, excsc and hav
+  --theme NAME      dark or light (default dark)
+  --unit NAME       radians, degrees, gradians, turns or pi (default degrees)
+  --msaa N          samples per pixel in the window: 1, 2, 4 or 8 (default 4)
+
+Saving without opening a window:
+  --snapshot FILE   save the diagram as FILE (.png or .svg)
+  --record FILE     save a sweep of θ as an animated GIF (.gif), or as numbered
+                    PNG frames named after FILE (.png)
+  --table FILE      save the values of the functions over a range of θ, as a
+                    .csv or .json file
+  --from RADIANS    θ at the start of the recording or table (default 0)
+  --to RADIANS      θ the recording sweeps to, or the table ends at (default 2π)
+  --step RADIANS    the step between the rows of the table (default π/12)
+  --seconds N       length of the recording (default 4)
+  --fps N           frames per second of the recording (default 30)
+  --loops N         times a GIF plays over again (default forever)";
+
+/// What the binary was asked to do.
+pub enum Command {
+    /// Open the window as usual.
+    Run,
+    Snapshot {
+        path: PathBuf,
+        format: ImageFormat,
+    },
+    Record {
+        path: PathBuf,
+        /// An animated GIF, or else a numbered sequence of PNG frames.
+        gif: bool,
+        recording: Recording,
+    },
+    Table {
+        path: PathBuf,
+        format: TableFormat,
+        table: Table,
+    },
+}
+
+/// The parsed command line.
+pub struct Cli {
+    pub command: Command,
+    pub config: Config,
+}
+
+fn value(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("{} needs a value", flag))
+}
+
+fn number(flag: &str, value: Option<String>) -> Result<f32, String> {
+    let value = value.ok_or_else(|| format!("{} needs a number", flag))?;
+    // `parse` takes nan and inf too, which nothing can be drawn at
+    value
+        .parse()
+        .ok()
+        .filter(|number: &f32| number.is_finite())
+        .ok_or_else(|| format!("{}: {} is not a number", flag, value))
+}
+
+fn size(value: Option<String>) -> Result<(f32, f32), String> {
+    let value = value.ok_or("--size needs a size such as 1200x720")?;
+    let mut parts = value.split('x').map(str::parse::<u16>);
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(width)), Some(Ok(height)), None) if width > 0 && height > 0 => {
+            Ok((width.into(), height.into()))
+        }
+        _ => Err(format!("--size: {} is not a size such as 1200x720", value)),
+    }
+}
+
+fn sweep(value: Option<String>) -> Result<Sweep, String> {
+    let value = value.ok_or("--sweep needs two angles such as -1.57,1.57, or full")?;
+    if value == "full" {
+        return Ok(Sweep::Full);
+    }
+    let mut parts = value.split(',').map(|part| part.trim().parse::<f32>());
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(from)), Some(Ok(to)), None) => {
+            Sweep::between(from, to).map_err(|err| format!("--sweep: {}", err))
+        }
+        _ => Err(format!(
+            "--sweep: {} is not two angles such as -1.57,1.57",
+            value
+        )),
+    }
+}
+
+fn functions(value: Option<String>) -> Result<Vec<Function>, String> {
+    let value = value.ok_or("--functions needs a list such as cos,sin,tan")?;
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            Function::from_name(name).ok_or_else(|| format!("--functions: no function {}", name))
+        })
+        .collect()
+}
+
+fn extension(path: &Path) -> String {
+    path.extension().map_or_else(String::new, |extension| {
+        extension.to_string_lossy().to_ascii_lowercase()
+    })
+}
+
+fn load_config(path: &str) -> Result<Config, String> {
+    let text = fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err))?;
+    Config::from_ron(&text).map_err(|err| format!("{}: {}", path, err))
+}
+
+/// The options that are followed by a value.
+const VALUE_FLAGS: [&str; 18] = [
+    "--config",
+    "--theta",
+    "--speed",
+    "--sweep",
+    "--size",
+    "--functions",
+    "--theme",
+    "--unit",
+    "--msaa",
+    "--snapshot",
+    "--record",
+    "--table",
+    "--from",
+    "--to",
+    "--step",
+    "--seconds",
+    "--fps",
+    "--loops",
+];
+
+/// Reads the command line, without the name of the program. The options it
+/// doesn't give are taken from the config file, if there is one, or else
+/// from `defaults`.
+pub fn parse(args: impl IntoIterator<Item = String>, defaults: Config) -> Result<Cli, String> {
+    // pair each option with its value first, so that a value that looks like
+    // an option, e.g. a file named --config, isn't taken for one
+    let mut options = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(flag) = args.next() {
+        let value = if VALUE_FLAGS.contains(&flag.as_str()) {
+            args.next()
+        } else {
+            None
+        };
+        options.push((flag, value));
+    }
+    if options
+        .iter()
+        .any(|(flag, _)| flag == "--help" || flag == "-h")
+    {
+        return Err(String::new());
+    }
+
+    // the config file is read first, wherever it's given, so that the
+    // other options can override it
+    let mut config = match options.iter().find(|(flag, _)| flag == "--config") {
+        Some((_, path)) => load_config(&value("--config", path.clone())?)?,
+        None => defaults,
+    };
+
+    let mut snapshot = None;
+    let mut record = None;
+    let mut table_path = None;
+    let mut recording = Recording::default();
+    let mut table = Table::default();
+
+    for (flag, arg) in options {
+        match flag.as_str() {
+            "--config" => {}
+            "--theta" => config.theta = number("--theta", arg)?,
+            "--paused" => config.paused = true,
+            "--snapping" => config.snapping = true,
+            "--speed" => config.speed = number("--speed", arg)?,
+            "--sweep" => config.sweep = sweep(arg)?,
+            "--size" => {
+                let (width, height) = size(arg)?;
+                config.width = width;
+                config.height = height;
+            }
+            "--fullscreen" => config.fullscreen = true,
+            "--functions" => config.functions = functions(arg)?,
+            "--theme" => {
+                let name = value("--theme", arg)?;
+                config.theme =
+                    Theme::from_name(&name).ok_or_else(|| format!("--theme: no theme {}", name))?;
+            }
+            "--unit" => {
+                let name = value("--unit", arg)?;
+                config.unit = AngleUnit::from_name(&name)
+                    .ok_or_else(|| format!("--unit: no unit {}", name))?;
+            }
+            "--msaa" => {
+                let count = value("--msaa", arg)?;
+                let samples = count
+                    .parse()
+                    .map_err(|_| format!("--msaa: {} is not a count", count))?;
+                config.msaa = Config::check_msaa(samples).map_err(|err| format!("--{}", err))?;
+            }
+            "--snapshot" => snapshot = Some(PathBuf::from(value("--snapshot", arg)?)),
+            "--record" => record = Some(PathBuf::from(value("--record", arg)?)),
+            "--table" => table_path = Some(PathBuf::from(value("--table", arg)?)),
+            "--from" => {
+                recording.from = number("--from", arg)?;
+                table.from = recording.from;
+            }
+            "--to" => {
+                recording.to = number("--to", arg)?;
+                table.to = recording.to;
+            }
+            "--step" => table.step = number("--step", arg)?,
+            "--seconds" => recording.seconds = number("--seconds", arg)?,
+            "--fps" => recording.fps = number("--fps", arg)?,
+            "--loops" => {
+                let count = value("--loops", arg)?;
+                recording.loops = Some(
+                    count
+                        .parse()
+                        .map_err(|_| format!("--loops: {} is not a count", count))?,
+                );
+            }
+            _ => return Err(format!("unknown argument {}", flag)),
+        }
+    }
+    if recording.fps <= 0. || recording.seconds <= 0. || table.step <= 0. {
+        return Err("--fps, --seconds and --step must be more than 0".into());
+    }
+    recording.check()?;
+    table.check()?;
+
+    let command = match (snapshot, record, table_path) {
+        (Some(path), None, None) => {
+            let format = match extension(&path).as_str() {
+                "png" => ImageFormat::Png,
+                "svg" => ImageFormat::Svg,
+                _ => return Err(format!("{}: can only save .png or .svg", path.display())),
+            };
+            Command::Snapshot { path, format }
+        }
+        (None, Some(path), None) => {
+            let gif = match extension(&path).as_str() {
+                "gif" => true,
+                "png" => false,
+                _ => return Err(format!("{}: can only record .gif or .png", path.display())),
+            };
+            Command::Record {
+                path,
+                gif,
+                recording,
+            }
+        }
+        (None, None, Some(path)) => {
+            let format = match extension(&path).as_str() {
+                "csv" => TableFormat::Csv,
+                "json" => TableFormat::Json,
+                _ => return Err(format!("{}: can only save .csv or .json", path.display())),
+            };
+            let diagram = config.diagram();
+            table.functions = diagram.shown;
+            table.unit = diagram.unit;
+            table.display = diagram.display;
+            Command::Table {
+                path,
+                format,
+                table,
+            }
+        }
+        (None, None, None) => Command::Run,
+        _ => {
+            return Err("only one of --snapshot, --record and --table can be used at a time".into())
+        }
+    };
+    Ok(Cli { command, config })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(args: &str) -> Result<Cli, String> {
+        parse(args.split_whitespace().map(String::from), Config::default())
+    }
+
+    fn config(args: &str) -> Config {
+        parse_str(args)
+            .unwrap_or_else(|err| panic!("{}", err))
+            .config
+    }
+
+    /// A config file in the temporary directory, unique to the test.
+    fn config_file(name: &str, text: &str) -> String {
+        let path = std::env::temp_dir().join(format!("trig-cli-{}.ron", name));
+        fs::write(&path, text).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn reads_each_option() {
+        assert_eq!(config("--theta 0.5").theta, 0.5);
+        assert!(config("--paused").paused);
+        assert!(config("--snapping").snapping);
+        assert_eq!(config("--speed 2").speed, 2.);
+        assert_eq!(
+            config("--sweep 0,3").sweep,
+            Sweep::PingPong { start: 0., end: 3. }
+        );
+        assert_eq!(config("--sweep full").sweep, Sweep::Full);
+        let sized = config("--size 800x600");
+        assert_eq!((sized.width, sized.height), (800., 600.));
+        assert!(config("--fullscreen").fullscreen);
+        assert_eq!(
+            config("--functions cos,hav").functions,
+            vec![Function::Cos, Function::Haversin]
+        );
+        assert_eq!(config("--theme light").theme, Theme::Light);
+        assert_eq!(config("--unit pi").unit, AngleUnit::PiFraction);
+        assert_eq!(config("--msaa 8").msaa, 8);
+        assert!(matches!(parse_str("").unwrap().command, Command::Run));
+    }
+
+    #[test]
+    fn options_override_the_config_file() {
+        let path = config_file("override", "(theta: 1, theme: light, paused: true)");
+        // wherever the file is given
+        for args in [
+            format!("--config {} --theta 2", path),
+            format!("--theta 2 --config {}", path),
+        ]
+        .iter()
+        {
+            let config = config(args);
+            assert_eq!(config.theta, 2.);
+            assert_eq!(config.theme, Theme::Light);
+            assert!(config.paused);
+        }
+    }
+
+    #[test]
+    fn values_that_look_like_options_are_values() {
+        // the file named --config is where the snapshot goes, not a config
+        // file to read
+        let err = parse_str("--snapshot --config").err().unwrap();
+        assert!(err.contains("can only save .png or .svg"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_missing_and_bad_values() {
+        for args in [
+            "--theta",
+            "--theta half",
+            "--theta nan",
+            "--theta inf",
+            "--speed -inf",
+            "--step nan",
+            "--sweep 0,nan",
+            "--size 800",
+            "--sweep 1",
+            "--sweep 1,1",
+            "--size 0x600",
+            "--functions sine",
+            "--theme blue",
+            "--unit miles",
+            "--msaa 3",
+            "--msaa many",
+            "--loops -1",
+            "--fps 0",
+            "--fps 1e6 --seconds 1e6",
+            "--step 1e-9",
+            "--from 0 --to 1e30",
+            "--config /nonexistent/trig.ron",
+        ]
+        .iter()
+        {
+            assert!(parse_str(args).is_err(), "{}", args);
+        }
+        let err = parse_str("--msaa 3").err().unwrap();
+        assert!(err.contains("1, 2, 4 or 8"), "{}", err);
+        let path = config_file("msaa", "(msaa: 16)");
+        assert!(parse_str(&format!("--config {}", path)).is_err());
+        let path = config_file("nan", "(theta: NaN)");
+        assert!(parse_str(&format!("--config {}", path)).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_options() {
+        assert_eq!(
+            parse_str("--theta 1 --colour red").err().unwrap(),
+            "unknown argument --colour"
+        );
+        // help is asked for with an empty error, which prints the usage
+        assert_eq!(parse_str("--theta 1 --help").err().unwrap(), "");
+    }
+
+    #[test]
+    fn picks_the_command_from_the_file_name() {
+        assert!(matches!(
+            parse_str("--snapshot out.svg").unwrap().command,
+            Command::Snapshot {
+                format: ImageFormat::Svg,
+                ..
+            }
+        ));
+        match parse_str("--record out.gif --seconds 2 --fps 10 --loops 3 --from 1")
+            .unwrap()
+            .command
+        {
+            Command::Record { gif, recording, .. } => {
+                assert!(gif);
+                assert_eq!(recording.seconds, 2.);
+                assert_eq!(recording.fps, 10.);
+                assert_eq!(recording.loops, Some(3));
+                assert_eq!(recording.from, 1.);
+            }
+            _ => panic!("not a recording"),
+        }
+        match parse_str("--table out.json --functions sin --step 0.5 --to 1")
+            .unwrap()
+            .command
+        {
+            Command::Table { format, table, .. } => {
+                assert_eq!(format, TableFormat::Json);
+                assert_eq!(table.functions, vec![Function::Sin]);
+                assert_eq!((table.step, table.to), (0.5, 1.));
+            }
+            _ => panic!("not a table"),
+        }
+        assert!(parse_str("--snapshot out.jpg").is_err());
+        assert!(parse_str("--record out.mp4").is_err());
+        assert!(parse_str("--table out.txt").is_err());
+        assert!(parse_str("--snapshot a.png --table b.csv").is_err());
+    }
+}
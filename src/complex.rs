@@ -0,0 +1,239 @@
+use crate::angle::AngleUnit;
+use crate::geometry::Point;
+use std::f32::consts::PI;
+use std::fmt;
+use std::ops::Mul;
+
+/// A complex number, drawn on the plane with the real part along x and the
+/// imaginary part along y, so that the unit circle is every `e^{iθ}`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Complex {
+    pub re: f32,
+    pub im: f32,
+}
+
+impl Complex {
+    pub const ONE: Complex = Complex { re: 1., im: 0. };
+
+    pub fn new(re: f32, im: f32) -> Self {
+        Self { re, im }
+    }
+
+    /// `r e^{iθ}`.
+    pub fn from_polar(r: f32, theta: f32) -> Self {
+        Self::new(r * theta.cos(), r * theta.sin())
+    }
+
+    /// `e^{iθ} = cos θ + i sin θ`, the point at θ on the unit circle.
+    pub fn cis(theta: f32) -> Self {
+        Self::from_polar(1., theta)
+    }
+
+    pub fn abs(self) -> f32 {
+        self.re.hypot(self.im)
+    }
+
+    /// The angle from the positive real axis, in `(-π, π]`.
+    pub fn arg(self) -> f32 {
+        self.im.atan2(self.re)
+    }
+
+    /// Where this is drawn on a circle of `radius` pixels.
+    pub fn to_point(self, radius: f32) -> Point {
+        Point::new(self.re * radius, self.im * radius)
+    }
+
+    /// The number drawn at `point` on a circle of `radius` pixels.
+    pub fn from_point(point: Point, radius: f32) -> Self {
+        Self::new(point.x / radius, point.y / radius)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+/// e.g. `0.866 + 0.500i`.
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // no "-0.000" when the value only just dips below zero
+        let im = if self.im.abs() < 5e-4 { 0. } else { self.im };
+        let re = if self.re.abs() < 5e-4 { 0. } else { self.re };
+        let sign = if im < 0. { '-' } else { '+' };
+        write!(f, "{:.3} {} {:.3}i", re, sign, im.abs())
+    }
+}
+
+/// The `n` numbers whose `n`-th power is 1, `e^{2πik/n}`, starting at 1 and
+/// going anticlockwise. They are the corners of a regular polygon inscribed
+/// in the unit circle.
+pub fn roots_of_unity(n: u32) -> Vec<Complex> {
+    (0..n)
+        .map(|k| Complex::cis(2. * PI * k as f32 / n as f32))
+        .collect()
+}
+
+/// What is drawn on top of the circle when it's shown as the complex plane.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ComplexPlane {
+    pub enabled: bool,
+    /// The number `e^{iθ}` is multiplied by, if any.
+    pub factor: Option<Complex>,
+    /// How many roots of unity to show, if any.
+    pub roots: Option<u32>,
+}
+
+impl ComplexPlane {
+    /// Where the factor starts when it's turned on: a little outside the
+    /// circle, so that both the turning and the scaling show.
+    pub const DEFAULT_FACTOR: Complex = Complex { re: 1.2, im: 0.5 };
+    pub const MIN_ROOTS: u32 = 3;
+    pub const MAX_ROOTS: u32 = 12;
+
+    pub fn toggle_factor(&mut self) {
+        self.factor = match self.factor {
+            Some(_) => None,
+            None => Some(Self::DEFAULT_FACTOR),
+        };
+    }
+
+    /// One more root of unity, from none to [`Self::MIN_ROOTS`] up to
+    /// [`Self::MAX_ROOTS`] and back to none.
+    pub fn next_roots(&mut self) {
+        self.roots = match self.roots {
+            None => Some(Self::MIN_ROOTS),
+            Some(n) if n >= Self::MAX_ROOTS => None,
+            Some(n) => Some(n + 1),
+        };
+    }
+
+    /// `e^{iθ}` times the factor, if there is one.
+    pub fn product(&self, theta: f32) -> Option<Complex> {
+        self.factor.map(|factor| Complex::cis(theta) * factor)
+    }
+
+    /// The lines of the readout, e.g.
+    /// `e^{iθ} = cos θ + i sin θ = 0.866 + 0.500i`.
+    pub fn readout(&self, theta: f32, unit: AngleUnit) -> String {
+        let mut lines = vec![format!(
+            "e^{{iθ}} = cos θ + i sin θ = {}",
+            Complex::cis(theta)
+        )];
+        if let (Some(factor), Some(product)) = (self.factor, self.product(theta)) {
+            lines.push(format!(
+                "w = {} = {:.3} e^{{i {}}}",
+                factor,
+                factor.abs(),
+                unit.format(factor.arg())
+            ));
+            lines.push(format!(
+                "e^{{iθ}} w = {}, turned by {} and scaled by {:.3}",
+                product,
+                unit.format(factor.arg()),
+                factor.abs()
+            ));
+        }
+        if let Some(n) = self.roots {
+            lines.push(format!("z^{} = 1 at e^{{2πik/{}}}, k = 0…{}", n, n, n - 1));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Where the "Re" and "Im" labels go: just past where the circle crosses the
+/// positive real and imaginary axes, clear of the axes themselves.
+pub fn axis_labels(radius: f32, offset: f32) -> [(&'static str, Point); 2] {
+    [
+        ("Re", Point::new(radius + 2. * offset, -offset)),
+        ("Im", Point::new(-offset, radius + 2. * offset)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn assert_close(a: Complex, b: Complex) {
+        assert!(
+            (a.re - b.re).abs() < 1e-5 && (a.im - b.im).abs() < 1e-5,
+            "{} != {}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn multiplying_turns_and_scales() {
+        let w = Complex::from_polar(2., 0.5);
+        let product = Complex::cis(1.) * w;
+        assert!((product.abs() - 2.).abs() < 1e-5);
+        assert!((product.arg() - 1.5).abs() < 1e-5);
+        // i² = -1
+        let i = Complex::new(0., 1.);
+        assert_close(i * i, Complex::new(-1., 0.));
+    }
+
+    #[test]
+    fn writes_both_parts() {
+        assert_eq!(Complex::cis(FRAC_PI_2).to_string(), "0.000 + 1.000i");
+        assert_eq!(Complex::new(0.5, -0.25).to_string(), "0.500 - 0.250i");
+        assert_eq!(Complex::new(-1e-6, -1e-6).to_string(), "0.000 + 0.000i");
+    }
+
+    #[test]
+    fn roots_of_unity_are_roots_of_unity() {
+        let roots = roots_of_unity(5);
+        assert_eq!(roots.len(), 5);
+        assert_eq!(roots[0], Complex::ONE);
+        for root in roots {
+            let power = (0..5).fold(Complex::ONE, |power, _| power * root);
+            assert_close(power, Complex::ONE);
+        }
+    }
+
+    #[test]
+    fn points_round_trip() {
+        let z = Complex::new(0.3, -0.7);
+        assert_close(Complex::from_point(z.to_point(200.), 200.), z);
+    }
+
+    #[test]
+    fn cycles_the_roots_and_back_to_none() {
+        let mut plane = ComplexPlane::default();
+        plane.next_roots();
+        assert_eq!(plane.roots, Some(ComplexPlane::MIN_ROOTS));
+        for _ in ComplexPlane::MIN_ROOTS..ComplexPlane::MAX_ROOTS {
+            plane.next_roots();
+        }
+        assert_eq!(plane.roots, Some(ComplexPlane::MAX_ROOTS));
+        plane.next_roots();
+        assert_eq!(plane.roots, None);
+    }
+
+    #[test]
+    fn readout_shows_eulers_formula() {
+        let mut plane = ComplexPlane::default();
+        assert_eq!(
+            plane.readout(0., AngleUnit::Degrees),
+            "e^{iθ} = cos θ + i sin θ = 1.000 + 0.000i"
+        );
+        plane.factor = Some(Complex::new(0., 2.));
+        plane.roots = Some(4);
+        let readout = plane.readout(0., AngleUnit::Degrees);
+        let lines: Vec<_> = readout.lines().collect();
+        assert_eq!(lines[1], "w = 0.000 + 2.000i = 2.000 e^{i 90.0°}");
+        assert_eq!(
+            lines[2],
+            "e^{iθ} w = 0.000 + 2.000i, turned by 90.0° and scaled by 2.000"
+        );
+        assert_eq!(lines[3], "z^4 = 1 at e^{2πik/4}, k = 0…3");
+    }
+}
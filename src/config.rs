@@ -0,0 +1,253 @@
+use crate::angle::{AngleUnit, Snapping};
+use crate::animation::{AnimationSettings, Sweep};
+use crate::function::Function;
+use crate::palette::Theme;
+use crate::scene::Diagram;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// The state the app starts in, e.g. for a lesson. Read from a RON file, in
+/// which anything left out keeps its default:
+///
+/// ```ron
+/// (
+///     theta: 0.5236,
+///     paused: true,
+///     functions: [cos, sin],
+///     theme: light,
+///     unit: pi,
+/// )
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// θ in radians.
+    #[serde(deserialize_with = "finite")]
+    pub theta: f32,
+    pub paused: bool,
+    /// How fast θ turns, in radians per second.
+    #[serde(deserialize_with = "finite")]
+    pub speed: f32,
+    /// The part of the circle θ turns over.
+    #[serde(deserialize_with = "sweep")]
+    pub sweep: Sweep,
+    /// Whether dragged angles snap to multiples of 15°.
+    pub snapping: bool,
+    /// The size of the window, in logical pixels.
+    #[serde(deserialize_with = "positive")]
+    pub width: f32,
+    #[serde(deserialize_with = "positive")]
+    pub height: f32,
+    pub fullscreen: bool,
+    /// The functions that are shown, the rest start hidden.
+    pub functions: Vec<Function>,
+    pub theme: Theme,
+    pub unit: AngleUnit,
+    /// How many samples each pixel of the window gets, to smooth the edges,
+    /// one of [`Config::MSAA_SAMPLES`].
+    #[serde(deserialize_with = "msaa")]
+    pub msaa: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            theta: 0.,
+            paused: false,
+            speed: AnimationSettings::default().speed,
+            sweep: Sweep::default(),
+            snapping: Snapping::default().enabled,
+            width: 1200.,
+            height: 720.,
+            fullscreen: false,
+            functions: Function::BASIC.to_vec(),
+            theme: Theme::default(),
+            unit: AngleUnit::default(),
+            msaa: 4,
+        }
+    }
+}
+
+/// RON reads `NaN` and `inf` as numbers too, but nothing can be drawn at them.
+fn finite<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+    let number = f32::deserialize(deserializer)?;
+    if number.is_finite() {
+        Ok(number)
+    } else {
+        Err(serde::de::Error::custom(format!(
+            "{} is not a number",
+            number
+        )))
+    }
+}
+
+fn positive<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+    let number = finite(deserializer)?;
+    if number > 0. {
+        Ok(number)
+    } else {
+        Err(serde::de::Error::custom(format!(
+            "a size has to be more than 0, not {}",
+            number
+        )))
+    }
+}
+
+fn msaa<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let samples = u32::deserialize(deserializer)?;
+    Config::check_msaa(samples).map_err(serde::de::Error::custom)
+}
+
+fn sweep<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Sweep, D::Error> {
+    Sweep::deserialize(deserializer)?
+        .check()
+        .map_err(serde::de::Error::custom)
+}
+
+impl Config {
+    /// The sample counts the renderer supports.
+    pub const MSAA_SAMPLES: [u32; 4] = [1, 2, 4, 8];
+
+    /// `samples` if the renderer supports it, or else an error naming the
+    /// ones it does.
+    pub fn check_msaa(samples: u32) -> Result<u32, String> {
+        if Self::MSAA_SAMPLES.contains(&samples) {
+            Ok(samples)
+        } else {
+            Err(format!("msaa must be 1, 2, 4 or 8, not {}", samples))
+        }
+    }
+
+    pub fn from_ron(text: &str) -> Result<Self, ron::Error> {
+        ron::from_str(text)
+    }
+
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    /// The animation settings, with the speed kept within the ones the app
+    /// can be set to.
+    pub fn animation(&self) -> AnimationSettings {
+        AnimationSettings {
+            speed: self
+                .speed
+                .clamp(AnimationSettings::MIN_SPEED, AnimationSettings::MAX_SPEED),
+            sweep: self.sweep,
+            ..AnimationSettings::default()
+        }
+    }
+
+    pub fn snapping(&self) -> Snapping {
+        Snapping {
+            enabled: self.snapping,
+            ..Snapping::default()
+        }
+    }
+
+    /// The diagram as the app would first show it.
+    pub fn diagram(&self) -> Diagram {
+        Diagram {
+            theta: self.theta,
+            width: self.width,
+            height: self.height,
+            // in the order the app gives their labels priority
+            shown: Function::ALL
+                .iter()
+                .copied()
+                .filter(|function| self.functions.contains(function))
+                .collect(),
+            unit: self.unit,
+            theme: self.theme,
+            ..Diagram::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_partial_files() {
+        let config = Config::from_ron(
+            "(theta: 0.5, paused: true, functions: [sin, hav, cos], theme: light, unit: pi)",
+        )
+        .unwrap();
+        assert_eq!(config.theta, 0.5);
+        assert!(config.paused);
+        assert_eq!(config.theme, Theme::Light);
+        assert_eq!(config.unit, AngleUnit::PiFraction);
+        assert_eq!(config.width, Config::default().width);
+        assert_eq!(
+            config.diagram().shown,
+            vec![Function::Cos, Function::Sin, Function::Haversin]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_names() {
+        assert!(Config::from_ron("(functions: [sine])").is_err());
+        assert!(Config::from_ron("(colour: light)").is_err());
+    }
+
+    #[test]
+    fn rejects_numbers_that_are_not_finite() {
+        for text in [
+            "(theta: NaN)",
+            "(theta: inf)",
+            "(speed: -inf)",
+            "(width: NaN)",
+            "(height: 0)",
+        ]
+        .iter()
+        {
+            assert!(Config::from_ron(text).is_err(), "{}", text);
+        }
+        assert_eq!(Config::from_ron("(theta: -1)").unwrap().theta, -1.);
+    }
+
+    #[test]
+    fn rejects_unsupported_msaa() {
+        assert_eq!(Config::from_ron("(msaa: 8)").unwrap().msaa, 8);
+        let err = Config::from_ron("(msaa: 3)").unwrap_err().to_string();
+        assert!(err.contains("1, 2, 4 or 8"), "{}", err);
+    }
+
+    #[test]
+    fn reads_sweeps() {
+        let config = Config::from_ron("(sweep: ping_pong(start: 0, end: 1.5))").unwrap();
+        assert_eq!(
+            config.animation().sweep,
+            Sweep::PingPong {
+                start: 0.,
+                end: 1.5
+            }
+        );
+        assert_eq!(
+            Config::from_ron("(sweep: full)").unwrap().sweep,
+            Sweep::Full
+        );
+        assert!(Config::from_ron("(sweep: ping_pong(start: 1, end: 0))").is_err());
+    }
+
+    #[test]
+    fn round_trips() {
+        let config = Config {
+            fullscreen: true,
+            speed: 2.,
+            sweep: Sweep::PRESETS[1],
+            ..Config::default()
+        };
+        let text = config.to_ron().unwrap();
+        assert_eq!(Config::from_ron(&text).unwrap(), config);
+    }
+
+    #[test]
+    fn keeps_the_speed_in_range() {
+        let config = Config {
+            speed: 100.,
+            ..Config::default()
+        };
+        assert_eq!(config.animation().speed, AnimationSettings::MAX_SPEED);
+    }
+}
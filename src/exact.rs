@@ -0,0 +1,158 @@
+use crate::function::Function;
+use std::f64::consts::PI;
+use std::fmt;
+
+/// How close θ has to be to a special angle, in radians, to count as on it.
+pub const EXACT_TOLERANCE: f32 = 1e-4;
+
+/// The exact value of a trig function at a special angle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExactValue {
+    Undefined,
+    Value {
+        negative: bool,
+        magnitude: &'static str,
+    },
+}
+
+impl ExactValue {
+    fn new(sign: f64, magnitude: Option<&'static str>) -> Self {
+        match magnitude {
+            None => ExactValue::Undefined,
+            Some(magnitude) => ExactValue::Value {
+                negative: sign < 0. && magnitude != "0",
+                magnitude,
+            },
+        }
+    }
+
+    /// Whether the value is a whole number, e.g. `-1` rather than `√3/2`.
+    pub fn is_integer(&self) -> bool {
+        match self {
+            ExactValue::Undefined => false,
+            ExactValue::Value { magnitude, .. } => magnitude.chars().all(|c| c.is_ascii_digit()),
+        }
+    }
+}
+
+impl fmt::Display for ExactValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExactValue::Undefined => write!(f, "undefined"),
+            ExactValue::Value {
+                negative: true,
+                magnitude,
+            } => write!(f, "-{}", magnitude),
+            ExactValue::Value { magnitude, .. } => write!(f, "{}", magnitude),
+        }
+    }
+}
+
+/// The magnitudes of cos, sin, tan, cot, sec and csc at the reference angles
+/// 0°, 30°, 45°, 60° and 90°. `None` is undefined.
+#[rustfmt::skip]
+const TABLE: [[Option<&str>; 6]; 5] = [
+    [Some("1"),    Some("0"),    Some("0"),    None,         Some("1"),    None],
+    [Some("√3/2"), Some("1/2"),  Some("1/√3"), Some("√3"),   Some("2/√3"), Some("2")],
+    [Some("1/√2"), Some("1/√2"), Some("1"),    Some("1"),    Some("√2"),   Some("√2")],
+    [Some("1/2"),  Some("√3/2"), Some("√3"),   Some("1/√3"), Some("2"),    Some("2/√3")],
+    [Some("0"),    Some("1"),    None,         Some("0"),    None,         Some("1")],
+];
+
+/// The special angle θ is on, as a whole number of 15° steps in `0..24`.
+///
+/// Only multiples of 30° and 45° are special.
+pub fn special_angle(theta: f32, tolerance: f32) -> Option<u32> {
+    let step = PI / 12.;
+    let steps = f64::from(theta) / step;
+    let nearest = steps.round();
+    if ((steps - nearest) * step).abs() > f64::from(tolerance) {
+        return None;
+    }
+    let index = (nearest as i64).rem_euclid(24) as u32;
+    // multiples of 2 or 3 steps, i.e. of 30° or 45°
+    if matches!(index % 6, 0 | 2 | 3 | 4) {
+        Some(index)
+    } else {
+        None
+    }
+}
+
+/// The exact value of `function` at θ, if θ is within `tolerance` of one of
+/// 0, π/6, π/4, π/3, π/2 or their reflections.
+///
+/// Only the six basic functions have exact values here.
+pub fn exact_value(function: Function, theta: f32, tolerance: f32) -> Option<ExactValue> {
+    let index = special_angle(theta, tolerance)?;
+    // fold into the first quadrant to get the reference angle
+    let in_half = index % 12;
+    let reference = match in_half.min(12 - in_half) {
+        0 => 0,
+        2 => 1,
+        3 => 2,
+        4 => 3,
+        _ => 4,
+    };
+
+    let angle = f64::from(index) * PI / 12.;
+    // the signs of the exactly representable 0 values don't matter
+    let (sin, cos) = angle.sin_cos();
+    let column = match function {
+        Function::Cos => 0,
+        Function::Sin => 1,
+        Function::Tan => 2,
+        Function::Cot => 3,
+        Function::Sec => 4,
+        Function::Csc => 5,
+        _ => return None,
+    };
+    let sign = match function {
+        Function::Cos | Function::Sec => cos,
+        Function::Sin | Function::Csc => sin,
+        _ => sin * cos,
+    };
+    Some(ExactValue::new(sign, TABLE[reference][column]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_3, FRAC_PI_4, FRAC_PI_6, PI};
+
+    fn exact(function: Function, theta: f32) -> Option<String> {
+        exact_value(function, theta, EXACT_TOLERANCE).map(|value| value.to_string())
+    }
+
+    #[test]
+    fn first_quadrant() {
+        assert_eq!(exact(Function::Cos, FRAC_PI_6).unwrap(), "√3/2");
+        assert_eq!(exact(Function::Sin, FRAC_PI_6).unwrap(), "1/2");
+        assert_eq!(exact(Function::Cos, FRAC_PI_4).unwrap(), "1/√2");
+        assert_eq!(exact(Function::Tan, FRAC_PI_3).unwrap(), "√3");
+        assert_eq!(exact(Function::Csc, FRAC_PI_3).unwrap(), "2/√3");
+        assert_eq!(exact(Function::Cot, 0.).unwrap(), "undefined");
+        assert_eq!(exact(Function::Tan, FRAC_PI_2).unwrap(), "undefined");
+        assert_eq!(exact(Function::Cos, FRAC_PI_2).unwrap(), "0");
+    }
+
+    #[test]
+    fn reflections_get_the_right_sign() {
+        assert_eq!(exact(Function::Cos, 5. * FRAC_PI_6).unwrap(), "-√3/2");
+        assert_eq!(exact(Function::Sin, 5. * FRAC_PI_4).unwrap(), "-1/√2");
+        assert_eq!(exact(Function::Tan, 5. * FRAC_PI_4).unwrap(), "1");
+        assert_eq!(exact(Function::Cot, 2. * FRAC_PI_3).unwrap(), "-1/√3");
+        assert_eq!(exact(Function::Sec, PI).unwrap(), "-1");
+        assert_eq!(exact(Function::Sin, PI).unwrap(), "0");
+        assert_eq!(exact(Function::Sin, -FRAC_PI_6).unwrap(), "-1/2");
+    }
+
+    #[test]
+    fn only_matches_special_angles() {
+        assert_eq!(exact(Function::Cos, 0.3), None);
+        // 15° is a multiple of π/12 but not a special angle
+        assert_eq!(exact(Function::Cos, PI / 12.), None);
+        assert_eq!(exact(Function::Cos, FRAC_PI_6 + 1e-3), None);
+        assert!(exact(Function::Cos, FRAC_PI_6 + 1e-5).is_some());
+        assert_eq!(exact(Function::Versin, 0.), None);
+    }
+}
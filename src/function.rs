@@ -0,0 +1,190 @@
+use crate::geometry::{Values, ASYMPTOTE_TOLERANCE};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::FRAC_PI_2;
+
+/// Where cos θ is zero, in `[0, 2π)`.
+const COS_ZEROS: [f32; 2] = [FRAC_PI_2, 3. * FRAC_PI_2];
+/// Where sin θ is zero, in `[0, 2π]`.
+const SIN_ZEROS: [f32; 3] = [0., 2. * FRAC_PI_2, 4. * FRAC_PI_2];
+
+/// The trig functions drawn on the unit circle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Function {
+    Cos,
+    Sin,
+    Tan,
+    Cot,
+    Sec,
+    Csc,
+    Versin,
+    Coversin,
+    Exsec,
+    Excsc,
+    #[serde(rename = "hav")]
+    Haversin,
+}
+
+impl Function {
+    pub const ALL: [Function; 11] = [
+        Function::Cos,
+        Function::Sin,
+        Function::Tan,
+        Function::Cot,
+        Function::Sec,
+        Function::Csc,
+        Function::Versin,
+        Function::Coversin,
+        Function::Exsec,
+        Function::Excsc,
+        Function::Haversin,
+    ];
+
+    /// The six functions of the classic unit-circle diagram.
+    pub const BASIC: [Function; 6] = [
+        Function::Cos,
+        Function::Sin,
+        Function::Tan,
+        Function::Cot,
+        Function::Sec,
+        Function::Csc,
+    ];
+
+    /// The older functions still used in surveying and navigation.
+    pub const ARCHAIC: [Function; 5] = [
+        Function::Versin,
+        Function::Coversin,
+        Function::Exsec,
+        Function::Excsc,
+        Function::Haversin,
+    ];
+
+    pub fn is_archaic(self) -> bool {
+        Function::ARCHAIC.contains(&self)
+    }
+
+    /// The function called `name`, as written by [`Function::name`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        Function::ALL
+            .iter()
+            .copied()
+            .find(|function| function.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Function::Cos => "cos",
+            Function::Sin => "sin",
+            Function::Tan => "tan",
+            Function::Cot => "cot",
+            Function::Sec => "sec",
+            Function::Csc => "csc",
+            Function::Versin => "versin",
+            Function::Coversin => "coversin",
+            Function::Exsec => "exsec",
+            Function::Excsc => "excsc",
+            Function::Haversin => "hav",
+        }
+    }
+
+    /// Whether the function heads off to infinity, i.e. has asymptotes.
+    pub fn is_unbounded(self) -> bool {
+        !self.asymptotes().is_empty()
+    }
+
+    /// The angles at which the function is undefined, over one period of
+    /// the graph.
+    pub fn asymptotes(self) -> &'static [f32] {
+        match self {
+            Function::Tan | Function::Sec | Function::Exsec => &COS_ZEROS,
+            Function::Cot | Function::Csc | Function::Excsc => &SIN_ZEROS,
+            _ => &[],
+        }
+    }
+
+    /// Whether the function has a value at all, as opposed to sitting on one
+    /// of its asymptotes.
+    pub fn is_defined(self, values: &Values) -> bool {
+        match self {
+            Function::Tan | Function::Sec | Function::Exsec => {
+                values.cos.abs() >= ASYMPTOTE_TOLERANCE
+            }
+            Function::Cot | Function::Csc | Function::Excsc => {
+                values.sin.abs() >= ASYMPTOTE_TOLERANCE
+            }
+            _ => true,
+        }
+    }
+
+    pub fn value(self, values: &Values) -> f32 {
+        match self {
+            Function::Cos => values.cos,
+            Function::Sin => values.sin,
+            Function::Tan => values.tan,
+            Function::Cot => values.cot,
+            Function::Sec => values.sec,
+            Function::Csc => values.csc,
+            Function::Versin => values.versin,
+            Function::Coversin => values.coversin,
+            Function::Exsec => values.exsec,
+            Function::Excsc => values.excsc,
+            Function::Haversin => values.haversin,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_picks_the_matching_field() {
+        let values = Values::new(0.3);
+        assert_eq!(Function::Cos.value(&values), values.cos);
+        assert_eq!(Function::Cot.value(&values), values.cot);
+        assert_eq!(Function::Csc.value(&values), values.csc);
+        assert_eq!(Function::Haversin.value(&values), values.haversin);
+    }
+
+    #[test]
+    fn undefined_on_the_axes() {
+        let values = Values::new(FRAC_PI_2);
+        assert!(!Function::Tan.is_defined(&values));
+        assert!(!Function::Exsec.is_defined(&values));
+        assert!(Function::Cot.is_defined(&values));
+        assert!(Function::Cos.is_defined(&values));
+        assert!(!Function::Csc.is_defined(&Values::new(0.)));
+        assert!(Function::Tan.is_defined(&Values::new(FRAC_PI_2 - 0.01)));
+    }
+
+    #[test]
+    fn only_the_reciprocal_functions_are_unbounded() {
+        let unbounded: Vec<_> = Function::ALL
+            .iter()
+            .filter(|function| function.is_unbounded())
+            .collect();
+        assert_eq!(
+            unbounded,
+            vec![
+                &Function::Tan,
+                &Function::Cot,
+                &Function::Sec,
+                &Function::Csc,
+                &Function::Exsec,
+                &Function::Excsc
+            ]
+        );
+    }
+
+    #[test]
+    fn groups_cover_every_function_once() {
+        for function in Function::ALL.iter() {
+            assert_ne!(
+                Function::BASIC.contains(function),
+                function.is_archaic(),
+                "{:?}",
+                function
+            );
+        }
+    }
+}
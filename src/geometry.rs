@@ -0,0 +1,443 @@
+use crate::function::Function;
+use std::f32::consts::PI;
+
+/// How close cos θ (or sin θ) has to be to zero before tan θ (or cot θ) is
+/// treated as being on its asymptote, i.e. undefined.
+pub const ASYMPTOTE_TOLERANCE: f32 = 1e-4;
+
+/// How far below the x axis the haversine is drawn, as a fraction of the radius.
+pub const HAVERSIN_OFFSET: f32 = 0.04;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0., y: 0. };
+
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    pub fn new(start: Point, end: Point) -> Self {
+        Self { start, end }
+    }
+
+    pub fn length(&self) -> f32 {
+        let dx = self.end.x - self.start.x;
+        let dy = self.end.y - self.start.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    /// The point `t` of the way from `start` to `end`.
+    pub fn lerp(&self, t: f32) -> Point {
+        Point::new(
+            self.start.x + (self.end.x - self.start.x) * t,
+            self.start.y + (self.end.y - self.start.y) * t,
+        )
+    }
+
+    /// Splits the segment into dashes of length `dash` separated by `gap`.
+    pub fn dashes(&self, dash: f32, gap: f32) -> Vec<Segment> {
+        let length = self.length();
+        let mut dashes = Vec::new();
+        if length == 0. || dash <= 0. {
+            return dashes;
+        }
+        let mut from = 0.;
+        while from < length {
+            let to = (from + dash).min(length);
+            dashes.push(Segment::new(
+                self.lerp(from / length),
+                self.lerp(to / length),
+            ));
+            from = to + gap;
+        }
+        dashes
+    }
+
+    /// The part of the segment inside `bounds`, if any.
+    pub fn clip(&self, bounds: &Bounds) -> Option<Segment> {
+        // Liang-Barsky: narrow down the range of t against each edge in turn
+        let dx = self.end.x - self.start.x;
+        let dy = self.end.y - self.start.y;
+        let edges = [
+            (-dx, self.start.x - bounds.min.x),
+            (dx, bounds.max.x - self.start.x),
+            (-dy, self.start.y - bounds.min.y),
+            (dy, bounds.max.y - self.start.y),
+        ];
+        let (mut from, mut to) = (0f32, 1f32);
+        for &(p, q) in edges.iter() {
+            if p == 0. {
+                // parallel to the edge, so either all inside or all outside
+                if q < 0. {
+                    return None;
+                }
+            } else if p < 0. {
+                from = from.max(q / p);
+            } else {
+                to = to.min(q / p);
+            }
+        }
+        if from > to {
+            return None;
+        }
+        Some(Segment::new(self.lerp(from), self.lerp(to)))
+    }
+
+    /// The two barbs of an arrowhead of length `size` at the end of the segment.
+    pub fn arrowhead(&self, size: f32) -> [Segment; 2] {
+        let length = self.length();
+        if length == 0. {
+            return [Segment::new(self.end, self.end); 2];
+        }
+        let dx = (self.end.x - self.start.x) / length;
+        let dy = (self.end.y - self.start.y) / length;
+        let (sin, cos) = (PI / 6.).sin_cos();
+        let barb = |sin: f32| {
+            Segment::new(
+                self.end,
+                Point::new(
+                    self.end.x - size * (dx * cos - dy * sin),
+                    self.end.y - size * (dx * sin + dy * cos),
+                ),
+            )
+        };
+        [barb(sin), barb(-sin)]
+    }
+}
+
+/// An axis-aligned rectangle, e.g. the part of the world that is on screen.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    pub fn new(min: Point, max: Point) -> Self {
+        Self { min, max }
+    }
+
+    /// The bounds of a `width` by `height` rectangle centred on `centre`.
+    pub fn centered(centre: Point, width: f32, height: f32) -> Self {
+        Self::new(
+            Point::new(centre.x - width / 2., centre.y - height / 2.),
+            Point::new(centre.x + width / 2., centre.y + height / 2.),
+        )
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+    }
+
+    pub fn overlaps(&self, other: &Bounds) -> bool {
+        self.min.x < other.max.x
+            && other.min.x < self.max.x
+            && self.min.y < other.max.y
+            && other.min.y < self.max.y
+    }
+
+    /// The bounds with every edge moved `margin` inwards.
+    pub fn shrink(&self, margin: f32) -> Self {
+        Self::new(
+            Point::new(self.min.x + margin, self.min.y + margin),
+            Point::new(self.max.x - margin, self.max.y - margin),
+        )
+    }
+}
+
+/// The value of every trig function at θ.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Values {
+    pub cos: f32,
+    pub sin: f32,
+    pub tan: f32,
+    pub cot: f32,
+    pub sec: f32,
+    pub csc: f32,
+    pub versin: f32,
+    pub coversin: f32,
+    pub exsec: f32,
+    pub excsc: f32,
+    pub haversin: f32,
+}
+
+impl Values {
+    pub fn new(theta: f32) -> Self {
+        let (sin, cos) = theta.sin_cos();
+        let tan = theta.tan();
+        Self {
+            cos,
+            sin,
+            tan,
+            cot: tan.recip(),
+            sec: cos.recip(),
+            csc: sin.recip(),
+            versin: 1. - cos,
+            coversin: 1. - sin,
+            exsec: cos.recip() - 1.,
+            excsc: sin.recip() - 1.,
+            haversin: (1. - cos) / 2.,
+        }
+    }
+}
+
+/// Everything that is drawn on the unit circle for a given θ and radius.
+///
+/// All points are in world units with the centre of the circle at the origin.
+/// The lines of functions that are undefined at θ are collapsed to their start.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TrigGeometry {
+    pub theta: f32,
+    pub radius: f32,
+    pub values: Values,
+    /// The point on the circle at θ.
+    pub point: Point,
+    pub radius_line: Segment,
+    pub cos_line: Segment,
+    pub sin_line: Segment,
+    pub tan_line: Segment,
+    pub cot_line: Segment,
+    pub sec_line: Segment,
+    pub csc_line: Segment,
+    /// From the foot of the sine line to where the circle crosses the x axis.
+    pub versin_line: Segment,
+    /// From the foot of the cosine line to where the circle crosses the y axis.
+    pub coversin_line: Segment,
+    /// sec θ − 1 measured along the x axis from where the circle crosses it.
+    /// That's the part of the secant line outside the circle while sec θ > 1,
+    /// and runs back across the circle while sec θ is negative.
+    pub exsec_line: Segment,
+    /// csc θ − 1 measured along the y axis from where the circle crosses it,
+    /// likewise.
+    pub excsc_line: Segment,
+    /// Half of the versine, drawn just below it so the two don't overlap.
+    pub haversin_line: Segment,
+    /// cos θ is zero, so tan θ and sec θ are undefined.
+    pub tan_asymptote: bool,
+    /// sin θ is zero, so cot θ and csc θ are undefined.
+    pub cot_asymptote: bool,
+}
+
+impl TrigGeometry {
+    pub fn new(theta: f32, radius: f32) -> Self {
+        let values = Values::new(theta);
+
+        // cos = width
+        let x = radius * values.cos;
+        // sin = height
+        let y = radius * values.sin;
+        let point = Point::new(x, y);
+
+        let tan_asymptote = !Function::Tan.is_defined(&values);
+        let cot_asymptote = !Function::Cot.is_defined(&values);
+
+        // we use secant to calculate the x coordinate of the end
+        // of the tangent line, no tan function is actually used
+        // to draw the line. The tan function would be used to get
+        // the line's length. The ends are left unclamped, it's up
+        // to the renderer to clip them to what is on screen
+        let end_x = values.sec * radius;
+        // likewise cosecant gives the y coordinate of the end of
+        // the cotangent line
+        let end_y = values.csc * radius;
+        let tan_end =
+            |start: Point, end: Point| Segment::new(start, if tan_asymptote { start } else { end });
+        let cot_end =
+            |start: Point, end: Point| Segment::new(start, if cot_asymptote { start } else { end });
+
+        Self {
+            theta,
+            radius,
+            values,
+            point,
+            radius_line: Segment::new(Point::ORIGIN, point),
+            cos_line: Segment::new(Point::new(0., y), point),
+            sin_line: Segment::new(Point::new(x, 0.), point),
+            tan_line: tan_end(point, Point::new(end_x, 0.)),
+            cot_line: cot_end(point, Point::new(0., end_y)),
+            // secant and cosecant run along the axes from the centre to
+            // where the tangent line crosses them
+            sec_line: tan_end(Point::ORIGIN, Point::new(end_x, 0.)),
+            csc_line: cot_end(Point::ORIGIN, Point::new(0., end_y)),
+            versin_line: Segment::new(Point::new(x, 0.), Point::new(radius, 0.)),
+            coversin_line: Segment::new(Point::new(0., y), Point::new(0., radius)),
+            exsec_line: tan_end(Point::new(radius, 0.), Point::new(end_x, 0.)),
+            excsc_line: cot_end(Point::new(0., radius), Point::new(0., end_y)),
+            haversin_line: Segment::new(
+                Point::new(x, -HAVERSIN_OFFSET * radius),
+                Point::new((x + radius) / 2., -HAVERSIN_OFFSET * radius),
+            ),
+            tan_asymptote,
+            cot_asymptote,
+        }
+    }
+
+    /// The segment that is drawn for `function`.
+    pub fn segment(&self, function: Function) -> Segment {
+        match function {
+            Function::Cos => self.cos_line,
+            Function::Sin => self.sin_line,
+            Function::Tan => self.tan_line,
+            Function::Cot => self.cot_line,
+            Function::Sec => self.sec_line,
+            Function::Csc => self.csc_line,
+            Function::Versin => self.versin_line,
+            Function::Coversin => self.coversin_line,
+            Function::Exsec => self.exsec_line,
+            Function::Excsc => self.excsc_line,
+            Function::Haversin => self.haversin_line,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-3, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn point_is_on_the_circle() {
+        let geometry = TrigGeometry::new(1.2, 200.);
+        let p = geometry.point;
+        assert_close((p.x * p.x + p.y * p.y).sqrt(), 200.);
+        assert_eq!(geometry.radius_line.end, p);
+    }
+
+    #[test]
+    fn cos_and_sin_lines_are_width_and_height() {
+        let geometry = TrigGeometry::new(FRAC_PI_4, 100.);
+        let half = 100. * FRAC_PI_4.cos();
+        assert_eq!(geometry.cos_line.start, Point::new(0., geometry.point.y));
+        assert_close(geometry.cos_line.end.x, half);
+        assert_eq!(geometry.sin_line.start, Point::new(geometry.point.x, 0.));
+        assert_close(geometry.sin_line.end.y, half);
+    }
+
+    #[test]
+    fn tan_and_cot_lines_end_on_the_axes() {
+        let geometry = TrigGeometry::new(FRAC_PI_4, 100.);
+        let sec = 100. * 2f32.sqrt();
+        assert_close(geometry.tan_line.end.x, sec);
+        assert_close(geometry.tan_line.end.y, 0.);
+        assert_close(geometry.cot_line.end.x, 0.);
+        assert_close(geometry.cot_line.end.y, sec);
+        assert!(!geometry.tan_asymptote);
+        assert!(!geometry.cot_asymptote);
+    }
+
+    #[test]
+    fn sec_and_csc_lines_share_the_tangent_ends() {
+        let geometry = TrigGeometry::new(2.5, 100.);
+        assert_eq!(geometry.sec_line.start, Point::ORIGIN);
+        assert_eq!(geometry.sec_line.end, geometry.tan_line.end);
+        assert_eq!(geometry.csc_line.start, Point::ORIGIN);
+        assert_eq!(geometry.csc_line.end, geometry.cot_line.end);
+    }
+
+    #[test]
+    fn archaic_lengths_match_their_values() {
+        // one angle in each quadrant, the values are signed but the lengths
+        // aren't
+        for &theta in [1., 2.5, 3.8, 5.5].iter() {
+            let geometry = TrigGeometry::new(theta, 100.);
+            let values = geometry.values;
+            assert_close(geometry.versin_line.length(), 100. * values.versin);
+            assert_close(geometry.coversin_line.length(), 100. * values.coversin);
+            assert_close(geometry.exsec_line.length(), 100. * values.exsec.abs());
+            assert_close(geometry.excsc_line.length(), 100. * values.excsc.abs());
+            assert_close(geometry.haversin_line.length(), 100. * values.haversin);
+        }
+    }
+
+    #[test]
+    fn undefined_lines_collapse_on_asymptotes() {
+        let geometry = TrigGeometry::new(FRAC_PI_2, 100.);
+        assert!(geometry.tan_asymptote);
+        assert!(!geometry.cot_asymptote);
+        assert_eq!(geometry.tan_line.length(), 0.);
+        assert_eq!(geometry.sec_line.length(), 0.);
+        assert_eq!(geometry.exsec_line.length(), 0.);
+
+        let geometry = TrigGeometry::new(PI, 100.);
+        assert!(geometry.cot_asymptote);
+        assert_eq!(geometry.cot_line.length(), 0.);
+        assert_eq!(geometry.csc_line.length(), 0.);
+    }
+
+    #[test]
+    fn lines_near_asymptotes_are_not_clamped() {
+        let geometry = TrigGeometry::new(FRAC_PI_2 - 0.001, 100.);
+        assert!(!geometry.tan_asymptote);
+        assert!(geometry.tan_line.end.x > 10_000.);
+    }
+
+    #[test]
+    fn clipping_keeps_the_part_on_screen() {
+        let bounds = Bounds::centered(Point::ORIGIN, 200., 100.);
+        let segment = Segment::new(Point::ORIGIN, Point::new(1000., 0.));
+        let clipped = segment.clip(&bounds).unwrap();
+        assert_eq!(clipped, Segment::new(Point::ORIGIN, Point::new(100., 0.)));
+
+        let inside = Segment::new(Point::new(-10., -10.), Point::new(10., 10.));
+        assert_eq!(inside.clip(&bounds), Some(inside));
+
+        let outside = Segment::new(Point::new(-500., 60.), Point::new(500., 60.));
+        assert_eq!(outside.clip(&bounds), None);
+
+        let diagonal = Segment::new(Point::new(-1000., -1000.), Point::new(1000., 1000.));
+        let clipped = diagonal.clip(&bounds).unwrap();
+        assert_close(clipped.start.y, -50.);
+        assert_close(clipped.end.x, 50.);
+    }
+
+    #[test]
+    fn arrowhead_points_back_along_the_segment() {
+        let segment = Segment::new(Point::ORIGIN, Point::new(100., 0.));
+        let [left, right] = segment.arrowhead(10.);
+        assert_eq!(left.start, segment.end);
+        assert_close(left.length(), 10.);
+        assert!(left.end.x < 100.);
+        assert_close(left.end.y, -right.end.y);
+    }
+
+    #[test]
+    fn dashes_cover_the_segment() {
+        let segment = Segment::new(Point::ORIGIN, Point::new(25., 0.));
+        let dashes = segment.dashes(4., 2.);
+        assert_eq!(dashes.len(), 5);
+        assert_eq!(dashes[0], Segment::new(Point::ORIGIN, Point::new(4., 0.)));
+        assert_eq!(
+            dashes[4],
+            Segment::new(Point::new(24., 0.), Point::new(25., 0.))
+        );
+    }
+
+    #[test]
+    fn values_match_std() {
+        let values = Values::new(0.7);
+        assert_close(values.tan, 0.7f32.tan());
+        assert_close(values.cot, 0.7f32.tan().recip());
+        assert_close(values.sec, 0.7f32.cos().recip());
+        assert_close(values.csc, 0.7f32.sin().recip());
+    }
+}
@@ -0,0 +1,254 @@
+use crate::angle::AngleUnit;
+use crate::geometry::{Bounds, Point, Segment};
+use std::f32::consts::PI;
+
+/// The spacing of the fine grid lines and short ticks, in units of the radius,
+/// unless there'd be too many of them, see [`MAX_MAJOR_LINES`].
+pub const MINOR_STEP: f32 = 0.1;
+/// How many minor steps there are to a major one, i.e. 0.5 units of radius.
+/// Major steps get bolder lines, longer ticks and numeric labels.
+pub const MINOR_PER_MAJOR: i32 = 5;
+/// The most major lines drawn across each axis. Zoomed out further than that,
+/// the steps grow to 1, 2 or 5 times a power of ten units of radius.
+pub const MAX_MAJOR_LINES: f32 = 50.;
+/// Minor lines closer together than this, in pixels, are left out.
+const MIN_MINOR_SPACING: f32 = 6.;
+/// The angle between the ticks around the circle.
+pub const CIRCLE_TICK_STEP: f32 = PI / 12.;
+/// Angle labels closer together than this along the circle, in pixels, are
+/// thinned out.
+const MIN_ANGLE_LABEL_SPACING: f32 = 40.;
+
+/// A piece of text anchored by its centre.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+    pub position: Point,
+    pub text: String,
+}
+
+/// The coordinate grid, measured in units of the radius of a circle at the
+/// origin, together with the angle ticks around that circle.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Grid {
+    pub minor_lines: Vec<Segment>,
+    pub major_lines: Vec<Segment>,
+    /// Ticks along both axes and around the circle.
+    pub ticks: Vec<Segment>,
+    pub labels: Vec<Label>,
+}
+
+impl Grid {
+    /// The grid covering `bounds`, with ticks `tick` pixels long (twice that
+    /// for major ones) and angles labelled in `unit`.
+    pub fn new(radius: f32, bounds: &Bounds, tick: f32, unit: AngleUnit) -> Self {
+        let mut grid = Grid::default();
+        if radius <= 0. {
+            return grid;
+        }
+        grid.add_axes(radius, bounds, tick);
+        grid.add_circle(radius, tick, unit);
+        grid
+    }
+
+    fn add_axes(&mut self, radius: f32, bounds: &Bounds, tick: f32) {
+        let span = (bounds.max.x - bounds.min.x).max(bounds.max.y - bounds.min.y);
+        let major_step = match major_step(span / radius) {
+            Some(major_step) => major_step,
+            None => return,
+        };
+        let step = major_step / MINOR_PER_MAJOR as f32 * radius;
+        let show_minor = step >= MIN_MINOR_SPACING;
+        // the indices of the grid lines that fall within `from..=to`
+        let range = |from: f32, to: f32| (from / step).ceil() as i32..=(to / step).floor() as i32;
+        let label = |i: i32| format_coordinate(i as f32 * step / radius, major_step);
+
+        for i in range(bounds.min.x, bounds.max.x) {
+            let x = i as f32 * step;
+            let major = i % MINOR_PER_MAJOR == 0;
+            // the axes themselves are drawn elsewhere
+            if i != 0 && (major || show_minor) {
+                let line = Segment::new(Point::new(x, bounds.min.y), Point::new(x, bounds.max.y));
+                let length = if major { 2. * tick } else { tick };
+                self.ticks
+                    .push(Segment::new(Point::new(x, -length), Point::new(x, length)));
+                if major {
+                    self.major_lines.push(line);
+                    self.labels.push(Label {
+                        position: Point::new(x, -2. * tick - 10.),
+                        text: label(i),
+                    });
+                } else {
+                    self.minor_lines.push(line);
+                }
+            }
+        }
+
+        for i in range(bounds.min.y, bounds.max.y) {
+            let y = i as f32 * step;
+            let major = i % MINOR_PER_MAJOR == 0;
+            if i != 0 && (major || show_minor) {
+                let line = Segment::new(Point::new(bounds.min.x, y), Point::new(bounds.max.x, y));
+                let length = if major { 2. * tick } else { tick };
+                self.ticks
+                    .push(Segment::new(Point::new(-length, y), Point::new(length, y)));
+                if major {
+                    self.major_lines.push(line);
+                    self.labels.push(Label {
+                        position: Point::new(-2. * tick - 18., y),
+                        text: label(i),
+                    });
+                } else {
+                    self.minor_lines.push(line);
+                }
+            }
+        }
+    }
+
+    fn add_circle(&mut self, radius: f32, tick: f32, unit: AngleUnit) {
+        let count = (2. * PI / CIRCLE_TICK_STEP).round() as usize;
+        // label every tick if there's room, otherwise every second, third...
+        let spacing = radius * CIRCLE_TICK_STEP;
+        let every = [1, 2, 3, 6]
+            .iter()
+            .copied()
+            .find(|every| spacing * *every as f32 >= MIN_ANGLE_LABEL_SPACING)
+            .unwrap_or(count / 4);
+
+        for i in 0..count {
+            let theta = i as f32 * CIRCLE_TICK_STEP;
+            let (sin, cos) = theta.sin_cos();
+            let at = |distance: f32| Point::new(distance * cos, distance * sin);
+            // the quarter turns get longer ticks
+            let length = if i % (count / 4) == 0 {
+                2. * tick
+            } else {
+                tick
+            };
+            self.ticks
+                .push(Segment::new(at(radius), at(radius + length)));
+            if i % every == 0 {
+                self.labels.push(Label {
+                    position: at(radius + 2. * tick + 16.),
+                    text: unit.tick_label(theta),
+                });
+            }
+        }
+    }
+}
+
+/// The spacing of the major lines, in units of the radius, so that there are
+/// at most [`MAX_MAJOR_LINES`] of them across `span` units. None if there's
+/// no telling, e.g. for an infinite span.
+fn major_step(span: f32) -> Option<f32> {
+    let step = MINOR_STEP * MINOR_PER_MAJOR as f32;
+    let needed = span / MAX_MAJOR_LINES;
+    if !needed.is_finite() {
+        return None;
+    }
+    if needed <= step {
+        return Some(step);
+    }
+    let decade = 10f32.powf(needed.log10().floor());
+    [1., 2., 5., 10.]
+        .iter()
+        .map(|multiple| multiple * decade)
+        .find(|step| *step >= needed)
+}
+
+/// A coordinate in units of the radius, e.g. `-1.5`, with a decimal place
+/// only while the major lines are less than a unit apart.
+fn format_coordinate(value: f32, major_step: f32) -> String {
+    if major_step < 1. {
+        format!("{:.1}", value)
+    } else {
+        format!("{:.0}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds() -> Bounds {
+        Bounds::centered(Point::ORIGIN, 500., 300.)
+    }
+
+    #[test]
+    fn lines_every_tenth_of_the_radius() {
+        let grid = Grid::new(100., &bounds(), 4., AngleUnit::Degrees);
+        // x from -2.5 to 2.5 and y from -1.5 to 1.5, leaving out the axes
+        assert_eq!(grid.major_lines.len(), 10 + 6);
+        assert_eq!(grid.minor_lines.len(), 40 + 24);
+        assert!(grid
+            .major_lines
+            .iter()
+            .any(|line| line.start == Point::new(50., -150.)));
+    }
+
+    #[test]
+    fn labels_the_major_lines() {
+        let grid = Grid::new(100., &bounds(), 4., AngleUnit::Degrees);
+        let texts: Vec<_> = grid
+            .labels
+            .iter()
+            .map(|label| label.text.as_str())
+            .collect();
+        assert!(texts.contains(&"-2.5"));
+        assert!(texts.contains(&"1.0"));
+        assert!(!texts.contains(&"0.0"));
+        // 15° apart would be too tight at this size, so every 30°
+        assert!(texts.contains(&"30°"));
+        assert!(texts.contains(&"330°"));
+        assert!(!texts.contains(&"45°"));
+    }
+
+    #[test]
+    fn crowded_lines_and_labels_are_thinned_out() {
+        let grid = Grid::new(40., &bounds(), 4., AngleUnit::PiFraction);
+        assert!(grid.minor_lines.is_empty());
+        assert!(!grid.major_lines.is_empty());
+        let angles: Vec<_> = grid
+            .labels
+            .iter()
+            .map(|label| label.text.as_str())
+            .filter(|text| text.contains('π') || *text == "0")
+            .collect();
+        assert_eq!(angles, vec!["0", "π/2", "π", "3π/2"]);
+    }
+
+    #[test]
+    fn zoomed_out_grids_space_their_lines_out() {
+        // a tiny circle, or a huge viewport, would otherwise need tens of
+        // thousands of lines
+        for &(radius, width) in [(0.01, 500.), (100., 1e7)].iter() {
+            let bounds = Bounds::centered(Point::ORIGIN, width, width);
+            let grid = Grid::new(radius, &bounds, 4., AngleUnit::Degrees);
+            let per_axis = grid.major_lines.len() / 2;
+            assert!(per_axis > 10 && per_axis <= MAX_MAJOR_LINES as usize);
+            assert!(grid.minor_lines.len() <= 4 * grid.major_lines.len() + 8);
+        }
+
+        let bounds = Bounds::centered(Point::ORIGIN, 500., 500.);
+        let grid = Grid::new(1., &bounds, 4., AngleUnit::Degrees);
+        let texts: Vec<_> = grid
+            .labels
+            .iter()
+            .map(|label| label.text.as_str())
+            .collect();
+        // 500 units across, so a line every 10
+        assert!(texts.contains(&"-240"));
+        assert!(texts.contains(&"10"));
+        assert!(!texts.contains(&"5"));
+    }
+
+    #[test]
+    fn circle_ticks_stick_out_of_the_circle() {
+        let grid = Grid::new(100., &Bounds::default(), 4., AngleUnit::Degrees);
+        assert_eq!(grid.ticks.len(), 24);
+        assert_eq!(
+            grid.ticks[0],
+            Segment::new(Point::new(100., 0.), Point::new(108., 0.))
+        );
+        assert!((grid.ticks[1].length() - 4.).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,330 @@
+//! The hyperbolic functions on the unit hyperbola x² − y² = 1, drawn the way
+//! the circular ones are drawn on the unit circle.
+//!
+//! The hyperbolic angle t plays the part of θ: on the circle the sector from
+//! the x axis to the point at θ has an area of θ/2, and on the hyperbola the
+//! one to the point at t has an area of t/2. The app uses θ, wrapped into
+//! `(-π, π]`, as t, so that the two views can be compared at the same angle.
+
+use crate::function::Function;
+use crate::geometry::{Bounds, Point, Segment};
+use crate::scene::ARROW_SIZE;
+use std::f32::consts::PI;
+
+/// How many points one branch of the hyperbola is drawn with.
+const HYPERBOLA_SAMPLES: usize = 128;
+/// How many points the shaded area is drawn with per unit of t.
+const AREA_SAMPLES: f32 = 32.;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HyperbolicFunction {
+    Cosh,
+    Sinh,
+    Tanh,
+    Coth,
+}
+
+impl HyperbolicFunction {
+    pub const ALL: [HyperbolicFunction; 4] = [
+        HyperbolicFunction::Cosh,
+        HyperbolicFunction::Sinh,
+        HyperbolicFunction::Tanh,
+        HyperbolicFunction::Coth,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HyperbolicFunction::Cosh => "cosh",
+            HyperbolicFunction::Sinh => "sinh",
+            HyperbolicFunction::Tanh => "tanh",
+            HyperbolicFunction::Coth => "coth",
+        }
+    }
+
+    /// The circular function this one is the counterpart of, which it's
+    /// drawn in the colour of.
+    pub fn circular(self) -> Function {
+        match self {
+            HyperbolicFunction::Cosh => Function::Cos,
+            HyperbolicFunction::Sinh => Function::Sin,
+            HyperbolicFunction::Tanh => Function::Tan,
+            HyperbolicFunction::Coth => Function::Cot,
+        }
+    }
+
+    /// The value at `t`, or `None` where it's undefined, i.e. coth 0.
+    pub fn value(self, t: f32) -> Option<f32> {
+        match self {
+            HyperbolicFunction::Cosh => Some(t.cosh()),
+            HyperbolicFunction::Sinh => Some(t.sinh()),
+            HyperbolicFunction::Tanh => Some(t.tanh()),
+            HyperbolicFunction::Coth if t == 0. => None,
+            HyperbolicFunction::Coth => Some(t.tanh().recip()),
+        }
+    }
+}
+
+/// The hyperbolic angle shown for θ, which is θ wrapped into `(-π, π]`.
+pub fn parameter(theta: f32) -> f32 {
+    PI - (PI - theta).rem_euclid(2. * PI)
+}
+
+/// The θ in `[0, 2π)` that shows the hyperbolic angle `t`, the inverse of
+/// [`parameter`].
+pub fn theta_for(t: f32) -> f32 {
+    t.rem_euclid(2. * PI)
+}
+
+/// The hyperbolic angle of the point on the right branch that lies in the
+/// direction of `point`, as far as [`parameter`] goes. Points outside the
+/// asymptotes give the largest angle on their side.
+pub fn parameter_at(point: Point) -> f32 {
+    let slope = if point.x > 0. {
+        point.y / point.x
+    } else {
+        point.y.signum()
+    };
+    let limit = PI.tanh();
+    slope.clamp(-limit, limit).atanh().clamp(-PI, PI)
+}
+
+/// Everything that is drawn on the unit hyperbola for a given t and radius,
+/// with the centre at the origin.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct HyperbolicGeometry {
+    pub t: f32,
+    pub radius: f32,
+    /// The point on the right branch at t, (cosh t, sinh t).
+    pub point: Point,
+    pub radius_line: Segment,
+    /// Across from the y axis to the point, like the cosine line.
+    pub cosh_line: Segment,
+    /// Up from the x axis to the point, like the sine line.
+    pub sinh_line: Segment,
+    /// Up the tangent to the vertex at (1, 0), to where the line from the
+    /// centre through the point crosses it.
+    pub tanh_line: Segment,
+    /// Across the line y = 1 to where the line through the point crosses it,
+    /// collapsed to its start at t = 0 where coth is undefined.
+    pub coth_line: Segment,
+}
+
+impl HyperbolicGeometry {
+    pub fn new(t: f32, radius: f32) -> Self {
+        let x = radius * t.cosh();
+        let y = radius * t.sinh();
+        let point = Point::new(x, y);
+        let coth_start = Point::new(0., radius);
+        let coth_end = match HyperbolicFunction::Coth.value(t) {
+            Some(coth) => Point::new(radius * coth, radius),
+            None => coth_start,
+        };
+
+        Self {
+            t,
+            radius,
+            point,
+            radius_line: Segment::new(Point::ORIGIN, point),
+            cosh_line: Segment::new(Point::new(0., y), point),
+            sinh_line: Segment::new(Point::new(x, 0.), point),
+            tanh_line: Segment::new(
+                Point::new(radius, 0.),
+                Point::new(radius, radius * t.tanh()),
+            ),
+            coth_line: Segment::new(coth_start, coth_end),
+        }
+    }
+
+    /// The segment that is drawn for `function`.
+    pub fn segment(&self, function: HyperbolicFunction) -> Segment {
+        match function {
+            HyperbolicFunction::Cosh => self.cosh_line,
+            HyperbolicFunction::Sinh => self.sinh_line,
+            HyperbolicFunction::Tanh => self.tanh_line,
+            HyperbolicFunction::Coth => self.coth_line,
+        }
+    }
+
+    /// What is drawn for `function`'s line: the part within `viewport`, with
+    /// an arrowhead where it runs off the edge, like
+    /// [`function_segments`](crate::scene::function_segments).
+    pub fn segments(&self, function: HyperbolicFunction, viewport: &Bounds) -> Vec<Segment> {
+        let segment = self.segment(function);
+        let mut segments = Vec::new();
+        if let Some(clipped) = segment.clip(&viewport.shrink(ARROW_SIZE / 2.)) {
+            segments.push(clipped);
+            if clipped.end != segment.end {
+                segments.extend(clipped.arrowhead(ARROW_SIZE).iter());
+            }
+        }
+        segments
+    }
+
+    /// The area between the x axis, the hyperbola and the radius line, which
+    /// is t/2 on the unit hyperbola, as a polygon starting at the centre.
+    pub fn area(&self) -> Vec<Point> {
+        let samples = ((self.t.abs() * AREA_SAMPLES).ceil() as usize).max(1);
+        let mut points = vec![Point::ORIGIN];
+        for i in 0..=samples {
+            let u = self.t * i as f32 / samples as f32;
+            points.push(Point::new(self.radius * u.cosh(), self.radius * u.sinh()));
+        }
+        points
+    }
+
+    /// The value of every function at t, one per line, e.g.
+    /// `cosh t = 1.54308`.
+    pub fn readout(&self) -> String {
+        let mut lines = vec![format!("t = {:.3}, the shaded area is t/2", self.t)];
+        for function in HyperbolicFunction::ALL.iter() {
+            let value = match function.value(self.t) {
+                Some(value) => format!("{:.5}", value),
+                None => "undefined".to_string(),
+            };
+            lines.push(format!("{} t = {}", function.name(), value));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Both branches of the hyperbola of `radius` far enough out to cross
+/// `viewport`, as polylines.
+pub fn hyperbola(radius: f32, viewport: &Bounds) -> Vec<Vec<Point>> {
+    if radius <= 0. {
+        return Vec::new();
+    }
+    let reach = viewport
+        .min
+        .y
+        .abs()
+        .max(viewport.max.y.abs())
+        .max(viewport.min.x.abs())
+        .max(viewport.max.x.abs());
+    let end = (reach / radius).asinh();
+    let branch = |side: f32| {
+        (0..=HYPERBOLA_SAMPLES)
+            .map(|i| {
+                let u = -end + 2. * end * i as f32 / HYPERBOLA_SAMPLES as f32;
+                Point::new(side * radius * u.cosh(), radius * u.sinh())
+            })
+            .collect()
+    };
+    vec![branch(1.), branch(-1.)]
+}
+
+/// The asymptotes y = ±x, which the hyperbola approaches but never meets,
+/// clipped to `viewport`.
+pub fn asymptotes(viewport: &Bounds) -> Vec<Segment> {
+    let reach = viewport
+        .min
+        .x
+        .abs()
+        .max(viewport.max.x.abs())
+        .max(viewport.min.y.abs())
+        .max(viewport.max.y.abs());
+    [1., -1.]
+        .iter()
+        .filter_map(|slope: &f32| {
+            Segment::new(
+                Point::new(-reach, -slope * reach),
+                Point::new(reach, slope * reach),
+            )
+            .clip(viewport)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-3, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn points_lie_on_the_hyperbola() {
+        for t in [-2., -0.5, 0., 1., 2.5].iter() {
+            let geometry = HyperbolicGeometry::new(*t, 1.);
+            let Point { x, y } = geometry.point;
+            assert_close(x * x - y * y, 1.);
+        }
+        let viewport = Bounds::centered(Point::ORIGIN, 800., 600.);
+        for branch in hyperbola(100., &viewport) {
+            for point in branch {
+                assert!((point.x * point.x - point.y * point.y - 1e4).abs() < 10.);
+            }
+        }
+    }
+
+    #[test]
+    fn lines_have_the_lengths_of_the_values() {
+        let geometry = HyperbolicGeometry::new(0.8, 100.);
+        for function in HyperbolicFunction::ALL.iter() {
+            let value = function.value(0.8).unwrap();
+            assert_close(geometry.segment(*function).length(), 100. * value.abs());
+        }
+        // coth has nowhere to go at t = 0
+        let flat = HyperbolicGeometry::new(0., 100.);
+        assert_eq!(flat.coth_line.start, flat.coth_line.end);
+    }
+
+    #[test]
+    fn area_is_half_the_angle() {
+        let t = 1.3;
+        let area = HyperbolicGeometry::new(t, 1.).area();
+        // shoelace formula over the closed polygon
+        let twice_area: f32 = area
+            .iter()
+            .zip(area.iter().cycle().skip(1))
+            .map(|(a, b)| a.x * b.y - b.x * a.y)
+            .sum();
+        assert_close(twice_area / 2., t / 2.);
+    }
+
+    #[test]
+    fn theta_wraps_into_a_half_turn_either_way() {
+        assert_close(parameter(0.5), 0.5);
+        assert_close(parameter(PI), PI);
+        assert_close(parameter(1.5 * PI), -0.5 * PI);
+        assert_close(parameter(-0.5), -0.5);
+        for t in [-3., -0.5, 0., 2., PI].iter() {
+            assert_close(parameter(theta_for(*t)), *t);
+        }
+    }
+
+    #[test]
+    fn dragging_follows_the_direction_of_the_cursor() {
+        let geometry = HyperbolicGeometry::new(0.7, 200.);
+        assert_close(parameter_at(geometry.point), 0.7);
+        assert_close(parameter_at(Point::new(-5., 10.)), PI);
+        assert_close(parameter_at(Point::new(10., -50.)), -PI);
+    }
+
+    #[test]
+    fn coth_runs_off_screen_with_an_arrowhead() {
+        let viewport = Bounds::centered(Point::ORIGIN, 400., 400.);
+        let geometry = HyperbolicGeometry::new(0.05, 100.);
+        assert_eq!(
+            geometry.segments(HyperbolicFunction::Coth, &viewport).len(),
+            3
+        );
+        assert_eq!(
+            geometry.segments(HyperbolicFunction::Sinh, &viewport).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn readout_lists_every_function() {
+        let readout = HyperbolicGeometry::new(0., 1.).readout();
+        assert_eq!(
+            readout,
+            "t = 0.000, the shaded area is t/2\n\
+             cosh t = 1.00000\n\
+             sinh t = 0.00000\n\
+             tanh t = 0.00000\n\
+             coth t = undefined"
+        );
+    }
+}
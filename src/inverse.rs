@@ -0,0 +1,409 @@
+use crate::angle::AngleUnit;
+use crate::function::Function;
+use crate::geometry::{Bounds, Point, Segment, Values};
+use std::f32::consts::{FRAC_PI_2, PI};
+
+/// Angles closer than this are the same angle, e.g. both solutions of
+/// asin 1.
+const SAME_ANGLE: f32 = 1e-4;
+
+/// The inverse functions, which go from a value back to the angles that
+/// have it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InverseFunction {
+    Asin,
+    Acos,
+    Atan,
+    Acot,
+}
+
+impl InverseFunction {
+    pub const ALL: [InverseFunction; 4] = [
+        InverseFunction::Asin,
+        InverseFunction::Acos,
+        InverseFunction::Atan,
+        InverseFunction::Acot,
+    ];
+
+    /// The inverse after this one, or `None` after the last to go back to
+    /// the usual diagram.
+    pub fn next(inverse: Option<Self>) -> Option<Self> {
+        match inverse {
+            None => Some(InverseFunction::ALL[0]),
+            Some(inverse) => {
+                let index = InverseFunction::ALL
+                    .iter()
+                    .position(|other| *other == inverse)
+                    .unwrap();
+                InverseFunction::ALL.get(index + 1).copied()
+            }
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            InverseFunction::Asin => "asin",
+            InverseFunction::Acos => "acos",
+            InverseFunction::Atan => "atan",
+            InverseFunction::Acot => "acot",
+        }
+    }
+
+    /// The function this is the inverse of.
+    pub fn function(self) -> Function {
+        match self {
+            InverseFunction::Asin => Function::Sin,
+            InverseFunction::Acos => Function::Cos,
+            InverseFunction::Atan => Function::Tan,
+            InverseFunction::Acot => Function::Cot,
+        }
+    }
+
+    /// The value the angle `theta` has, i.e. what this is the inverse of, or
+    /// `None` on an asymptote.
+    pub fn value_of(self, theta: f32) -> Option<f32> {
+        let values = Values::new(theta);
+        let function = self.function();
+        if function.is_defined(&values) {
+            Some(function.value(&values))
+        } else {
+            None
+        }
+    }
+
+    /// The values the function takes on, `None` if it takes on any value.
+    pub fn domain(self) -> Option<(f32, f32)> {
+        match self {
+            InverseFunction::Asin | InverseFunction::Acos => Some((-1., 1.)),
+            InverseFunction::Atan | InverseFunction::Acot => None,
+        }
+    }
+
+    /// The range of the principal value, e.g. `[-π/2, π/2]` for asin.
+    pub fn principal_range(self) -> (f32, f32) {
+        match self {
+            InverseFunction::Asin | InverseFunction::Atan => (-FRAC_PI_2, FRAC_PI_2),
+            InverseFunction::Acos | InverseFunction::Acot => (0., PI),
+        }
+    }
+
+    /// The principal value, or `None` if no angle has `value`.
+    pub fn principal(self, value: f32) -> Option<f32> {
+        if let Some((min, max)) = self.domain() {
+            if value < min || value > max {
+                return None;
+            }
+        }
+        Some(match self {
+            InverseFunction::Asin => value.asin(),
+            InverseFunction::Acos => value.acos(),
+            InverseFunction::Atan => value.atan(),
+            // cot θ = tan(π/2 - θ), which keeps acot continuous through 0
+            InverseFunction::Acot => FRAC_PI_2 - value.atan(),
+        })
+    }
+
+    /// Every angle in `[0, 2π)` with `value`, starting with the principal
+    /// value, which is wrapped into `[0, 2π)` as well.
+    pub fn angles(self, value: f32) -> Vec<f32> {
+        let principal = match self.principal(value) {
+            Some(principal) => principal,
+            None => return Vec::new(),
+        };
+        let other = match self {
+            // sin θ = sin(π - θ)
+            InverseFunction::Asin => PI - principal,
+            // cos θ = cos(-θ)
+            InverseFunction::Acos => -principal,
+            // tan and cot repeat every half turn
+            InverseFunction::Atan | InverseFunction::Acot => principal + PI,
+        };
+        let wrap = |angle: f32| {
+            let angle = angle.rem_euclid(2. * PI);
+            // rem_euclid can round up to 2π itself
+            if 2. * PI - angle < SAME_ANGLE {
+                0.
+            } else {
+                angle
+            }
+        };
+        let (principal, other) = (wrap(principal), wrap(other));
+        if (principal - other).abs() < SAME_ANGLE {
+            vec![principal]
+        } else {
+            vec![principal, other]
+        }
+    }
+
+    /// The line the value is picked on, between `min` and `max` along it for
+    /// the unbounded functions: the y axis for asin, the x axis for acos, the
+    /// tangent to the circle at (1, 0) for atan and at (0, 1) for acot.
+    pub fn guide(self, radius: f32, viewport: &Bounds) -> Segment {
+        match self {
+            InverseFunction::Asin => Segment::new(Point::new(0., -radius), Point::new(0., radius)),
+            InverseFunction::Acos => Segment::new(Point::new(-radius, 0.), Point::new(radius, 0.)),
+            InverseFunction::Atan => Segment::new(
+                Point::new(radius, viewport.min.y),
+                Point::new(radius, viewport.max.y),
+            ),
+            InverseFunction::Acot => Segment::new(
+                Point::new(viewport.min.x, radius),
+                Point::new(viewport.max.x, radius),
+            ),
+        }
+    }
+
+    /// Where `value` sits on the guide.
+    pub fn handle(self, value: f32, radius: f32) -> Point {
+        match self {
+            InverseFunction::Asin => Point::new(0., value * radius),
+            InverseFunction::Acos => Point::new(value * radius, 0.),
+            InverseFunction::Atan => Point::new(radius, value * radius),
+            InverseFunction::Acot => Point::new(value * radius, radius),
+        }
+    }
+
+    /// The radii out to each of the angles with `value`: the principal one
+    /// solid, the others dashed.
+    pub fn angle_lines(self, value: f32, radius: f32) -> Vec<Segment> {
+        let mut lines = Vec::new();
+        for (i, angle) in self.angles(value).into_iter().enumerate() {
+            let line = Segment::new(
+                Point::ORIGIN,
+                Point::new(radius * angle.cos(), radius * angle.sin()),
+            );
+            if i == 0 {
+                lines.push(line);
+            } else {
+                lines.extend(line.dashes(6., 4.));
+            }
+        }
+        lines
+    }
+
+    /// The value of the spot on the guide nearest to `point`, kept within
+    /// the domain.
+    pub fn value_at(self, point: Point, radius: f32) -> f32 {
+        if radius <= 0. {
+            return 0.;
+        }
+        let value = match self {
+            InverseFunction::Asin | InverseFunction::Atan => point.y / radius,
+            InverseFunction::Acos | InverseFunction::Acot => point.x / radius,
+        };
+        match self.domain() {
+            Some((min, max)) => value.clamp(min, max),
+            None => value,
+        }
+    }
+
+    /// e.g. `asin 0.5 = 30.0°, also 150.0°`, with every angle in `[0, 2π)`.
+    pub fn readout(self, value: f32, unit: AngleUnit) -> String {
+        let value_text = format_number(value);
+        let principal = match self.principal(value) {
+            Some(principal) => principal,
+            None => return format!("{} {} is undefined", self.name(), value_text),
+        };
+        let others: Vec<_> = self
+            .angles(value)
+            .into_iter()
+            .skip(1)
+            .map(|angle| unit.format(angle))
+            .collect();
+        let mut text = format!(
+            "{} {} = {}",
+            self.name(),
+            value_text,
+            unit.format(principal)
+        );
+        if !others.is_empty() {
+            text.push_str(", also ");
+            text.push_str(&others.join(", "));
+        }
+        text
+    }
+}
+
+/// `value` with up to three decimals and no trailing zeros, e.g. `0.5`.
+fn format_number(value: f32) -> String {
+    let text = format!("{:.3}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// A value being typed in, one key at a time.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ValueEntry(String);
+
+impl ValueEntry {
+    /// Adds `c` if it can be part of a number here, e.g. a minus sign only
+    /// at the start. Returns whether it was added.
+    pub fn push(&mut self, c: char) -> bool {
+        let fits = match c {
+            '0'..='9' => true,
+            '.' => !self.0.contains('.'),
+            '-' => self.0.is_empty(),
+            _ => false,
+        };
+        if fits {
+            self.0.push(c);
+        }
+        fits
+    }
+
+    pub fn pop(&mut self) {
+        self.0.pop();
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The number typed in so far, if it is one yet.
+    pub fn value(&self) -> Option<f32> {
+        self.0.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-4, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn principal_values_lie_in_their_range() {
+        for inverse in InverseFunction::ALL.iter() {
+            let (min, max) = inverse.principal_range();
+            for value in [-1., -0.5, 0., 0.3, 1.].iter() {
+                let principal = inverse.principal(*value).unwrap();
+                assert!(principal >= min && principal <= max, "{:?}", inverse);
+            }
+        }
+        assert_close(InverseFunction::Acot.principal(0.).unwrap(), FRAC_PI_2);
+        assert_close(InverseFunction::Acot.principal(-1.).unwrap(), 3. * PI / 4.);
+        assert_eq!(InverseFunction::Asin.principal(1.5), None);
+    }
+
+    #[test]
+    fn finds_every_angle_with_the_value() {
+        let angles = InverseFunction::Asin.angles(0.5);
+        assert_close(angles[0], PI / 6.);
+        assert_close(angles[1], 5. * PI / 6.);
+
+        // the principal value of acos -0.5 is 2π/3, the other is 4π/3
+        let angles = InverseFunction::Acos.angles(-0.5);
+        assert_close(angles[1], 4. * PI / 3.);
+
+        // negative principal values are wrapped into [0, 2π)
+        let angles = InverseFunction::Atan.angles(-1.);
+        assert_close(angles[0], 7. * PI / 4.);
+        assert_close(angles[1], 3. * PI / 4.);
+
+        for inverse in InverseFunction::ALL.iter() {
+            for angle in inverse.angles(0.7) {
+                let value = match inverse {
+                    InverseFunction::Asin => angle.sin(),
+                    InverseFunction::Acos => angle.cos(),
+                    InverseFunction::Atan => angle.tan(),
+                    InverseFunction::Acot => angle.tan().recip(),
+                };
+                assert_close(value, 0.7);
+            }
+        }
+    }
+
+    #[test]
+    fn undoes_its_function() {
+        for inverse in InverseFunction::ALL.iter() {
+            let value = inverse.value_of(1.).unwrap();
+            assert!(inverse
+                .angles(value)
+                .iter()
+                .any(|angle| (angle - 1.).abs() < 1e-4));
+        }
+        assert_eq!(InverseFunction::Acot.value_of(0.), None);
+    }
+
+    #[test]
+    fn repeated_angles_are_listed_once() {
+        assert_eq!(InverseFunction::Asin.angles(1.).len(), 1);
+        assert_eq!(InverseFunction::Acos.angles(1.), vec![0.]);
+        assert_eq!(InverseFunction::Acos.angles(-1.).len(), 1);
+        assert!(InverseFunction::Acos.angles(2.).is_empty());
+    }
+
+    #[test]
+    fn handle_follows_the_guide() {
+        for inverse in InverseFunction::ALL.iter() {
+            let handle = inverse.handle(0.4, 200.);
+            assert_close(inverse.value_at(handle, 200.), 0.4);
+        }
+        assert_eq!(
+            InverseFunction::Atan.handle(2., 100.),
+            Point::new(100., 200.)
+        );
+        // sine and cosine never leave [-1, 1]
+        assert_eq!(
+            InverseFunction::Acos.value_at(Point::new(500., 20.), 200.),
+            1.
+        );
+        assert_eq!(
+            InverseFunction::Acot.value_at(Point::new(500., 20.), 200.),
+            2.5
+        );
+    }
+
+    #[test]
+    fn readout_lists_the_angles() {
+        assert_eq!(
+            InverseFunction::Asin.readout(0.5, AngleUnit::Degrees),
+            "asin 0.5 = 30.0°, also 150.0°"
+        );
+        assert_eq!(
+            InverseFunction::Acos.readout(1., AngleUnit::Degrees),
+            "acos 1 = 0.0°"
+        );
+        assert_eq!(
+            InverseFunction::Asin.readout(2., AngleUnit::Degrees),
+            "asin 2 is undefined"
+        );
+    }
+
+    #[test]
+    fn cycles_through_the_inverses_and_back() {
+        let mut inverse = None;
+        for expected in InverseFunction::ALL.iter() {
+            inverse = InverseFunction::next(inverse);
+            assert_eq!(inverse, Some(*expected));
+        }
+        assert_eq!(InverseFunction::next(inverse), None);
+    }
+
+    #[test]
+    fn entry_only_takes_numbers() {
+        let mut entry = ValueEntry::default();
+        for c in "-0.5.-x2".chars() {
+            entry.push(c);
+        }
+        assert_eq!(entry.as_str(), "-0.52");
+        assert_eq!(entry.value(), Some(-0.52));
+        entry.clear();
+        entry.push('-');
+        assert_eq!(entry.value(), None);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::geometry::{Bounds, Point};
+use crate::view::CameraView;
+use crate::wave::WaveGraph;
+
+/// The share of the window's width given to the circle, the graph gets the rest.
+pub const CIRCLE_PANEL: f32 = 0.4;
+/// The circle is never drawn bigger than this before zooming, in pixels.
+pub const MAX_RADIUS: f32 = 200.;
+
+/// Where the circle goes in the window, for apps that don't want it fitted
+/// into its panel.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Placement {
+    /// The centre of the circle, in pixels from the middle of the window.
+    pub origin: Option<Point>,
+    /// The radius of the circle before zooming, in pixels.
+    pub radius: Option<f32>,
+}
+
+/// Where everything goes in a window of a given size: the circle on the left,
+/// the wave graph on the right, then zoomed and panned by the view.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Layout {
+    pub radius: f32,
+    /// Where the camera sits before any panning: far enough right that the
+    /// origin, and with it the circle, ends up in the middle of its panel.
+    pub home: Point,
+    /// The part of the scene the window shows.
+    pub viewport: Bounds,
+    pub graph: WaveGraph,
+}
+
+impl Layout {
+    pub fn new(width: f32, height: f32, view: &CameraView) -> Self {
+        Self::placed(width, height, view, &Placement::default())
+    }
+
+    /// The layout with the circle wherever `placement` puts it, or else fitted
+    /// into its panel.
+    pub fn placed(width: f32, height: f32, view: &CameraView, placement: &Placement) -> Self {
+        let panel = width * CIRCLE_PANEL;
+        // a tiny window shows nothing rather than a circle turned inside out
+        let fit_radius = placement.radius.unwrap_or_else(|| {
+            (panel / 2. - 20.)
+                .min(height / 2. - 20.)
+                .clamp(0., MAX_RADIUS)
+        });
+        // zooming scales the scene itself, so the lines stay as thin as ever
+        let radius = fit_radius * view.zoom;
+        // the camera sits opposite to where the origin should appear
+        let home = placement.origin.map_or_else(
+            || Point::new(width / 2. - panel / 2., 0.),
+            |origin| Point::new(-origin.x, -origin.y),
+        );
+        Self {
+            radius,
+            home,
+            viewport: view.visible(home, width, height),
+            graph: WaveGraph {
+                origin: Point::new((panel / 2. + 20.) * view.zoom, 0.),
+                width: (width - panel - 60.) * view.zoom,
+                amplitude: radius,
+                ..WaveGraph::default()
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn circle_sits_in_the_middle_of_its_panel() {
+        let layout = Layout::new(1200., 720., &CameraView::default());
+        assert_eq!(layout.radius, MAX_RADIUS);
+        // the panel is 480 wide, so the origin is 240 from the left edge
+        assert_eq!(layout.viewport.min.x, -240.);
+        assert_eq!(layout.viewport.max.y, 360.);
+        assert_eq!(layout.graph.origin.x, 260.);
+    }
+
+    #[test]
+    fn zooming_scales_the_scene() {
+        let view = CameraView {
+            zoom: 2.,
+            ..CameraView::default()
+        };
+        let layout = Layout::new(1200., 720., &view);
+        assert_eq!(layout.radius, 2. * MAX_RADIUS);
+        assert_eq!(layout.graph.width, 2. * (1200. - 480. - 60.));
+        // the window still shows as many pixels
+        assert_eq!(layout.viewport.max.x - layout.viewport.min.x, 1200.);
+    }
+
+    #[test]
+    fn placement_overrides_the_fit() {
+        let placement = Placement {
+            origin: Some(Point::new(-100., 50.)),
+            radius: Some(80.),
+        };
+        let layout = Layout::placed(1200., 720., &CameraView::default(), &placement);
+        assert_eq!(layout.radius, 80.);
+        // the origin is 500 from the left edge and 310 from the top
+        assert_eq!(layout.viewport.min.x, -500.);
+        assert_eq!(layout.viewport.max.y, 310.);
+        assert_eq!(
+            Layout::placed(1200., 720., &CameraView::default(), &Placement::default()),
+            Layout::new(1200., 720., &CameraView::default())
+        );
+    }
+}
@@ -0,0 +1,52 @@
+//! The math behind the trig visualization.
+//!
+//! Nothing in here depends on a window or a renderer, so it can be unit-tested
+//! and reused from other tools. The visualization itself is the Bevy plugin in
+//! `plugin`, behind the default `plugin` feature.
+
+pub mod angle;
+pub mod animation;
+pub mod annotation;
+pub mod complex;
+pub mod config;
+pub mod exact;
+pub mod function;
+pub mod geometry;
+pub mod grid;
+pub mod hyperbolic;
+pub mod inverse;
+pub mod layout;
+pub mod palette;
+#[cfg(feature = "plugin")]
+pub mod plugin;
+pub mod raster;
+pub mod readout;
+pub mod recording;
+pub mod scene;
+pub mod settings;
+pub mod svg;
+pub mod table;
+pub mod view;
+pub mod wave;
+
+pub use angle::{AngleUnit, SnapAngles, Snapping};
+pub use animation::{AnimationSettings, Direction, Easing, Sweep};
+pub use annotation::{place_labels, SegmentLabel};
+pub use complex::{Complex, ComplexPlane};
+pub use config::Config;
+pub use function::Function;
+pub use geometry::{Bounds, Point, Segment, TrigGeometry, Values};
+pub use grid::{Grid, Label};
+pub use hyperbolic::{HyperbolicFunction, HyperbolicGeometry};
+pub use inverse::{InverseFunction, ValueEntry};
+pub use layout::{Layout, Placement};
+pub use palette::{Rgba, Theme};
+#[cfg(feature = "plugin")]
+pub use plugin::TrigVisualizationPlugin;
+pub use readout::{format_value, ValueDisplay};
+pub use recording::Recording;
+pub use scene::{Diagram, Scene};
+pub use settings::Settings;
+pub use table::{Table, TableFormat};
+pub use view::CameraView;
+pub use wave::WaveGraph;
@@ -1,61 +1,79 @@
-mod consts;
-mod entity;
-mod pause_button;
-mod systems;
-mod utils;
+#[cfg(not(target_arch = "wasm32"))]
+mod cli;
+mod persistence;
+#[cfg(not(target_arch = "wasm32"))]
+mod snapshot;
+#[cfg(target_arch = "wasm32")]
+mod web;
 
-use crate::entity::{PauseButtonHovered, Paused, Radius, Theta};
 use bevy::prelude::*;
-use bevy_prototype_lyon::prelude::*;
+use bevy::window::WindowMode;
+use trig_geometry::plugin::color;
+use trig_geometry::TrigVisualizationPlugin;
 
 #[bevy_main]
 fn main() {
+    // the app starts as it was left last time, unless the options say otherwise
+    let defaults = persistence::load().unwrap_or_default().config();
+
+    // `--snapshot out.png --theta 0.785`, `--record sweep.gif` and `--table values.csv`
+    // save the diagram or its values without opening a window, the other
+    // options and `--config lesson.ron` set the state the app starts in
+    #[cfg(not(target_arch = "wasm32"))]
+    let config = match cli::parse(std::env::args().skip(1), defaults) {
+        Ok(cli::Cli {
+            command: cli::Command::Run,
+            config,
+        }) => config,
+        Ok(cli::Cli { command, config }) => {
+            if let Err(err) = snapshot::run(command, &config) {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Err(err) => {
+            if !err.is_empty() {
+                eprintln!("{}\n", err);
+            }
+            eprintln!("{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    #[cfg(target_arch = "wasm32")]
+    let config = defaults;
+
     let mut app = App::build();
 
     app.add_resource(WindowDescriptor {
         vsync: true,
-        width: 720.,
-        height: 720.,
+        width: config.width,
+        height: config.height,
         title: "Trig Visualization".into(),
+        mode: if config.fullscreen {
+            WindowMode::BorderlessFullscreen
+        } else {
+            WindowMode::Windowed
+        },
         ..Default::default()
     })
-    .add_resource(ClearColor(Color::rgb_linear(0.01, 0.01, 0.01)))
-    .add_resource(Msaa { samples: 4 })
+    .add_resource(ClearColor(color(config.theme.palette().background)))
+    .add_resource(Msaa {
+        samples: config.msaa,
+    })
     .add_plugins(DefaultPlugins);
 
     #[cfg(target_arch = "wasm32")]
-    app.add_plugin(bevy_webgl2::WebGL2Plugin);
-
-    app.add_plugin(ShapePlugin)
-        .add_resource(Theta(0.))
-        .add_resource(Radius(200.))
-        .add_resource(Paused(false))
-        .add_resource(PauseButtonHovered(false))
-        .init_resource::<pause_button::ButtonMaterials>()
-        .add_startup_system(setup.system())
-        .add_startup_system(systems::setup.system())
-        .add_startup_system(pause_button::setup.system());
+    app.add_plugin(bevy_webgl2::WebGL2Plugin)
+        .add_resource(web::WinitWebResizing::new())
+        .add_system(web::web_resize_system.system());
 
-    #[cfg(target_arch = "wasm32")]
-    app.add_system(systems::web_resize_system.system());
-
-    app.add_system(pause_button::pause_button_system.system())
-        .add_system(systems::animate_system.system())
-        .add_system(systems::radius_line_system.system())
-        .add_system(systems::circle_system.system())
-        .add_system(systems::cos_line_system.system())
-        .add_system(systems::sin_line_system.system())
-        .add_system(systems::tan_line_system.system())
-        .add_system(systems::cot_line_system.system())
-        .add_system(systems::mouse_click_system.system())
-        .add_system(systems::resize_circle_system.system())
-        .run();
-}
+    app.add_plugin(TrigVisualizationPlugin::new().with_config(config))
+        .add_system(persistence::settings_system.system());
+
+    #[cfg(not(target_arch = "wasm32"))]
+    app.add_system(snapshot::snapshot_system.system())
+        .add_system(snapshot::table_system.system());
 
-/// set up a simple 3D scene
-fn setup(commands: &mut Commands) {
-    commands
-        // 2d camera
-        .spawn(Camera2dBundle::default())
-        .spawn(CameraUiBundle::default());
+    app.run();
 }
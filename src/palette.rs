@@ -0,0 +1,170 @@
+use crate::function::Function;
+use serde::{Deserialize, Serialize};
+
+/// A colour with linear RGB components, as the renderers blend them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rgba {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Rgba {
+    pub const fn linear(r: f32, g: f32, b: f32) -> Self {
+        Self { r, g, b, a: 1. }
+    }
+
+    pub const fn with_alpha(self, a: f32) -> Self {
+        Self { a, ..self }
+    }
+
+    /// The 8-bit sRGB components, e.g. for a PNG or an SVG.
+    pub fn to_srgb8(self) -> [u8; 4] {
+        let encode = |c: f32| {
+            let c = c.clamp(0., 1.);
+            let srgb = if c <= 0.003_130_8 {
+                c * 12.92
+            } else {
+                1.055 * c.powf(1. / 2.4) - 0.055
+            };
+            (srgb * 255.).round() as u8
+        };
+        [
+            encode(self.r),
+            encode(self.g),
+            encode(self.b),
+            (self.a.clamp(0., 1.) * 255.).round() as u8,
+        ]
+    }
+}
+
+pub const COS: Rgba = Rgba::linear(0.8, 0.1, 0.1);
+pub const SIN: Rgba = Rgba::linear(0.1, 0.2, 0.9);
+pub const TAN: Rgba = Rgba::linear(0.1, 0.6, 0.1);
+pub const COT: Rgba = Rgba::linear(0.6, 0.6, 0.1);
+pub const SEC: Rgba = Rgba::linear(0.7, 0.2, 0.7);
+pub const CSC: Rgba = Rgba::linear(0.1, 0.6, 0.7);
+pub const VERSIN: Rgba = Rgba::linear(0.9, 0.4, 0.1);
+pub const COVERSIN: Rgba = Rgba::linear(0.4, 0.1, 0.9);
+pub const EXSEC: Rgba = Rgba::linear(0.9, 0.5, 0.6);
+pub const EXCSC: Rgba = Rgba::linear(0.4, 0.8, 0.8);
+pub const HAVERSIN: Rgba = Rgba::linear(0.9, 0.7, 0.3);
+
+/// The colours of everything but the functions, which look the same in every
+/// theme.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub background: Rgba,
+    pub axis: Rgba,
+    pub grid_minor: Rgba,
+    pub grid_major: Rgba,
+    pub grid_label: Rgba,
+    pub circle: Rgba,
+    pub radius: Rgba,
+    /// The θ label and the angle arc.
+    pub highlight: Rgba,
+    pub arc: Rgba,
+    pub text: Rgba,
+}
+
+pub const DARK: Palette = Palette {
+    background: Rgba::linear(0.01, 0.01, 0.01),
+    axis: Rgba::linear(0.3, 0.3, 0.3),
+    grid_minor: Rgba::linear(0.04, 0.04, 0.04),
+    grid_major: Rgba::linear(0.1, 0.1, 0.1),
+    grid_label: Rgba::linear(0.35, 0.35, 0.35),
+    circle: Rgba::linear(0.5, 0.5, 0.5),
+    // mid grey, 0.5 in sRGB
+    radius: Rgba::linear(0.214, 0.214, 0.214),
+    highlight: Rgba::linear(0.9, 0.9, 0.9),
+    arc: Rgba::linear(0.9, 0.9, 0.9).with_alpha(0.15),
+    text: Rgba::linear(0.7, 0.7, 0.7),
+};
+
+/// For printing and bright rooms.
+pub const LIGHT: Palette = Palette {
+    background: Rgba::linear(0.95, 0.95, 0.95),
+    axis: Rgba::linear(0.25, 0.25, 0.25),
+    grid_minor: Rgba::linear(0.8, 0.8, 0.8),
+    grid_major: Rgba::linear(0.6, 0.6, 0.6),
+    grid_label: Rgba::linear(0.2, 0.2, 0.2),
+    circle: Rgba::linear(0.1, 0.1, 0.1),
+    radius: Rgba::linear(0.214, 0.214, 0.214),
+    highlight: Rgba::linear(0.01, 0.01, 0.01),
+    arc: Rgba::linear(0.01, 0.01, 0.01).with_alpha(0.15),
+    text: Rgba::linear(0.03, 0.03, 0.03),
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 2] = [Theme::Dark, Theme::Light];
+
+    /// The theme after this one, wrapping around.
+    pub fn next(self) -> Self {
+        let index = Theme::ALL.iter().position(|theme| *theme == self).unwrap();
+        Theme::ALL[(index + 1) % Theme::ALL.len()]
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Theme::ALL
+            .iter()
+            .copied()
+            .find(|theme| theme.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Dark => "dark",
+            Theme::Light => "light",
+        }
+    }
+
+    pub fn palette(self) -> &'static Palette {
+        match self {
+            Theme::Dark => &DARK,
+            Theme::Light => &LIGHT,
+        }
+    }
+}
+
+pub fn function_color(function: Function) -> Rgba {
+    match function {
+        Function::Cos => COS,
+        Function::Sin => SIN,
+        Function::Tan => TAN,
+        Function::Cot => COT,
+        Function::Sec => SEC,
+        Function::Csc => CSC,
+        Function::Versin => VERSIN,
+        Function::Coversin => COVERSIN,
+        Function::Exsec => EXSEC,
+        Function::Excsc => EXCSC,
+        Function::Haversin => HAVERSIN,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_to_srgb() {
+        assert_eq!(Rgba::linear(0., 1., 0.5).to_srgb8(), [0, 255, 188, 255]);
+        assert_eq!(DARK.radius.to_srgb8()[0], 127);
+        assert_eq!(DARK.arc.to_srgb8()[3], 38);
+    }
+
+    #[test]
+    fn next_cycles_through_every_theme() {
+        assert_eq!(Theme::Dark.next(), Theme::Light);
+        assert_eq!(Theme::Light.next(), Theme::Dark);
+    }
+}
@@ -0,0 +1,154 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy::window::{WindowCloseRequested, WindowMode};
+use trig_geometry::plugin::ShownFunctions;
+use trig_geometry::{AngleUnit, AnimationSettings, Settings, Snapping, Theme};
+
+/// How long the settings have to stay the same before they're saved, so that
+/// dragging the window bigger doesn't write the file every frame.
+const SAVE_DELAY: f64 = 1.;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path() -> Option<std::path::PathBuf> {
+    Some(
+        dirs::config_dir()?
+            .join("trig-visualization")
+            .join(Settings::FILE_NAME),
+    )
+}
+
+/// The settings saved the last time the app ran, if there are any.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load() -> Option<Settings> {
+    let path = path()?;
+    let text = std::fs::read_to_string(&path).ok()?;
+    match Settings::from_ron(&text) {
+        Ok(settings) => Some(settings),
+        Err(err) => {
+            eprintln!("ignoring {}: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save(settings: &Settings) {
+    let path = match path() {
+        Some(path) => path,
+        None => return,
+    };
+    let result = settings
+        .to_ron()
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+        .and_then(|text| {
+            std::fs::create_dir_all(path.parent().unwrap())?;
+            std::fs::write(&path, text)
+        });
+    if let Err(err) = result {
+        eprintln!("couldn't save {}: {}", path.display(), err);
+    }
+}
+
+/// The key the settings are kept under in the browser's `localStorage`.
+#[cfg(target_arch = "wasm32")]
+const STORAGE_KEY: &str = "trig-visualization-settings";
+
+#[cfg(target_arch = "wasm32")]
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+/// The settings saved the last time the page was open, if there are any.
+#[cfg(target_arch = "wasm32")]
+pub fn load() -> Option<Settings> {
+    let text = storage()?.get_item(STORAGE_KEY).ok()??;
+    match Settings::from_ron(&text) {
+        Ok(settings) => Some(settings),
+        Err(err) => {
+            bevy::log::warn!("ignoring the saved settings: {}", err);
+            None
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn save(settings: &Settings) {
+    let result = match (storage(), settings.to_ron()) {
+        (Some(storage), Ok(text)) => storage.set_item(STORAGE_KEY, &text).is_ok(),
+        _ => false,
+    };
+    if !result {
+        bevy::log::warn!("couldn't save the settings");
+    }
+}
+
+#[derive(Default)]
+pub struct SettingsState {
+    /// What's saved, to write the user's changes over.
+    saved: Option<Settings>,
+    /// The settings in the first frame, which can come from the command line.
+    started: Option<Settings>,
+    /// The settings in the last frame, and when they changed to them.
+    latest: Option<Settings>,
+    changed_at: f64,
+    /// The size of the window the last time it wasn't fullscreen.
+    windowed_size: (f32, f32),
+    exit_reader: EventReader<AppExit>,
+    close_reader: EventReader<WindowCloseRequested>,
+}
+
+/// Saves what the user changes in the app once it's stopped changing for a
+/// moment, and straight away when the app is closed. Whatever the app was
+/// started with, e.g. from the command line, is left as it was saved.
+#[allow(clippy::too_many_arguments)]
+pub fn settings_system(
+    mut state: Local<SettingsState>,
+    time: Res<Time>,
+    shown: Res<ShownFunctions>,
+    theme: Res<Theme>,
+    unit: Res<AngleUnit>,
+    animation: Res<AnimationSettings>,
+    snapping: Res<Snapping>,
+    windows: Res<Windows>,
+    exits: Res<Events<AppExit>>,
+    closes: Res<Events<WindowCloseRequested>>,
+) {
+    let state = &mut *state;
+    let window = windows.get_primary().unwrap();
+    // a fullscreen window is the size of the screen, keep the size it had
+    // before. One that starts fullscreen never had another, so it keeps the
+    // screen size it started with, which counts as unchanged
+    if window.mode() == WindowMode::Windowed || state.started.is_none() {
+        state.windowed_size = (window.width(), window.height());
+    }
+    let settings = Settings {
+        functions: shown.ordered(),
+        theme: *theme,
+        unit: *unit,
+        speed: animation.speed,
+        snapping: snapping.enabled,
+        width: state.windowed_size.0,
+        height: state.windowed_size.1,
+    };
+    let (saved, started) = match (&state.saved, &state.started) {
+        (Some(saved), Some(started)) => (saved, started),
+        _ => {
+            state.saved = Some(load().unwrap_or_default());
+            state.started = Some(settings);
+            return;
+        }
+    };
+
+    let now = time.seconds_since_startup();
+    if state.latest.as_ref() != Some(&settings) {
+        state.latest = Some(settings.clone());
+        state.changed_at = now;
+    }
+    let closing = state.exit_reader.iter(&exits).next().is_some()
+        || state.close_reader.iter(&closes).next().is_some();
+    let changed = saved.with_changes(started, &settings);
+    if changed != *saved && (closing || now - state.changed_at >= SAVE_DELAY) {
+        save(&changed);
+        state.saved = Some(changed);
+    }
+}
@@ -0,0 +1,275 @@
+//! The visualization as a Bevy plugin, to embed in other apps.
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use trig_geometry::plugin::{SetTheta, TrigVisualizationPlugin};
+//! use trig_geometry::{Function, Point};
+//!
+//! App::build()
+//!     .add_plugins(DefaultPlugins)
+//!     .add_plugin(
+//!         TrigVisualizationPlugin::new()
+//!             .with_functions(&[Function::Cos, Function::Sin])
+//!             .with_origin(Point::new(-300., 0.))
+//!             .with_scale(150.)
+//!             .with_ui(false),
+//!     )
+//!     .run();
+//! ```
+//!
+//! Host apps read θ from the [`Theta`] resource or [`ThetaChanged`] events,
+//! and move it by sending [`SetTheta`] events, e.g. `SetTheta::script(1.)`.
+//! The text is drawn with the fonts in this repository's `assets/fonts`,
+//! which the app's assets need a copy of.
+
+// systems spell out the queries they take in full, which is how Bevy finds them
+#![allow(clippy::type_complexity)]
+
+mod camera;
+mod consts;
+pub mod entity;
+mod keyboard;
+mod pause_button;
+mod systems;
+mod utils;
+
+pub use consts::color;
+pub use entity::{
+    GridVisible, HyperbolicView, ImageFormat, Inverse, MainCamera, Paused, Radius, SetTheta,
+    ShownFunctions, SnapshotRequested, TableRequested, Theta, ThetaChanged, ThetaSource,
+};
+pub use keyboard::{Action, KeyMap};
+
+use crate::{
+    CameraView, ComplexPlane, Config, Function, Placement, Point, TrigGeometry, ValueDisplay,
+    WaveGraph,
+};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use entity::{HelpVisible, PauseButtonHovered, Viewport};
+
+/// The stages the plugin adds, both between `PRE_UPDATE` and `UPDATE`.
+pub mod stage {
+    /// Where the animation, the keyboard and the mouse send their
+    /// [`SetTheta`](super::SetTheta) requests, in that order.
+    pub const INPUT: &str = "trig_input";
+    /// Where θ is moved, so that it stays put from here to the end of the
+    /// frame and every system in `UPDATE` sees the same θ.
+    pub const APPLY_THETA: &str = "trig_apply_theta";
+}
+
+/// Draws the unit circle and the wave graph, animates θ and, unless turned
+/// off, adds the on-screen controls and the mouse and keyboard input.
+///
+/// The app still has to set up the window, e.g. with `DefaultPlugins`.
+#[derive(Debug, Clone)]
+pub struct TrigVisualizationPlugin {
+    config: Config,
+    placement: Placement,
+    keymap: KeyMap,
+    camera: bool,
+    ui: bool,
+    input: bool,
+}
+
+impl Default for TrigVisualizationPlugin {
+    fn default() -> Self {
+        Self {
+            config: Config::default(),
+            placement: Placement::default(),
+            keymap: KeyMap::default(),
+            camera: true,
+            ui: true,
+            input: true,
+        }
+    }
+}
+
+impl TrigVisualizationPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts in the state a config describes. Its window size, fullscreen
+    /// and MSAA are left to the app.
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The functions shown at first, the rest can be toggled on.
+    pub fn with_functions(mut self, functions: &[Function]) -> Self {
+        self.config.functions = functions.to_vec();
+        self
+    }
+
+    /// θ at first, in radians.
+    pub fn with_theta(mut self, theta: f32) -> Self {
+        self.config.theta = theta;
+        self
+    }
+
+    /// Puts the centre of the circle at `origin`, in pixels from the middle
+    /// of the window, rather than in the middle of the left part of it.
+    pub fn with_origin(mut self, origin: Point) -> Self {
+        self.placement.origin = Some(origin);
+        self
+    }
+
+    /// Draws the circle with a radius of `radius` pixels, rather than as big
+    /// as fits the window.
+    pub fn with_scale(mut self, radius: f32) -> Self {
+        self.placement.radius = Some(radius);
+        self
+    }
+
+    /// Whether to spawn the cameras the visualization is seen through, a 2D
+    /// one and, with the UI, a UI one. An app that has its own can leave them
+    /// out, and tag its 2D camera with [`MainCamera`] for the plugin to pan
+    /// and zoom.
+    pub fn with_camera(mut self, camera: bool) -> Self {
+        self.camera = camera;
+        self
+    }
+
+    /// Whether to add the readout, the buttons and the help text.
+    pub fn with_ui(mut self, ui: bool) -> Self {
+        self.ui = ui;
+        self
+    }
+
+    /// Uses `keymap` for the keyboard instead of the default bindings.
+    pub fn with_keymap(mut self, keymap: KeyMap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    /// Binds `key` to `action`, replacing whatever `key` did before. The
+    /// action's other keys still work, see [`KeyMap::unbind`] to drop them.
+    pub fn with_key(mut self, key: KeyCode, action: Action) -> Self {
+        self.keymap.bind(key, action);
+        self
+    }
+
+    /// Whether the mouse and the keyboard control θ and the view.
+    pub fn with_input(mut self, input: bool) -> Self {
+        self.input = input;
+        self
+    }
+}
+
+impl Plugin for TrigVisualizationPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        let config = &self.config;
+        app.add_plugin(ShapePlugin)
+            .add_event::<SetTheta>()
+            .add_event::<ThetaChanged>()
+            .add_resource(Theta(config.theta))
+            .add_resource(Radius(200.))
+            .add_resource(Paused(config.paused))
+            .add_resource(PauseButtonHovered(false))
+            .add_resource(HelpVisible(false))
+            .add_resource(GridVisible(true))
+            .add_resource(HyperbolicView(false))
+            .add_resource(SnapshotRequested(None))
+            .add_resource(TableRequested(None))
+            .add_resource(Viewport::default())
+            .add_resource(TrigGeometry::default())
+            .add_resource(WaveGraph::default())
+            .add_resource(ShownFunctions(config.functions.iter().copied().collect()))
+            .add_resource(config.unit)
+            .add_resource(config.theme)
+            .add_resource(config.animation())
+            .add_resource(config.snapping())
+            .add_resource(self.placement)
+            .init_resource::<Inverse>()
+            .init_resource::<ComplexPlane>()
+            .init_resource::<ValueDisplay>()
+            .init_resource::<CameraView>()
+            .add_resource(self.keymap.clone())
+            .init_resource::<systems::LineMaterials>()
+            .add_startup_system(systems::setup.system());
+        if self.camera {
+            app.add_startup_system(setup.system());
+            if self.ui {
+                app.add_startup_system(ui_setup.system());
+            }
+        }
+
+        app.add_stage_after(
+            bevy::app::stage::PRE_UPDATE,
+            stage::INPUT,
+            SystemStage::parallel(),
+        )
+        .add_stage_after(stage::INPUT, stage::APPLY_THETA, SystemStage::parallel())
+        .add_system_to_stage(stage::APPLY_THETA, systems::apply_theta_system.system())
+        .add_system_to_stage(stage::APPLY_THETA, systems::inverse_follow_system.system());
+
+        // requests sent later in the input stage win over earlier ones, and
+        // anything wins over the animation
+        if self.ui {
+            app.init_resource::<pause_button::ButtonMaterials>()
+                .add_startup_system(systems::ui_setup.system())
+                .add_startup_system(pause_button::setup.system())
+                .add_startup_system(keyboard::setup.system())
+                // before the mouse, which leaves θ alone over the buttons
+                .add_system_to_stage(stage::INPUT, pause_button::button_hover_system.system())
+                .add_system(pause_button::pause_button_system.system())
+                .add_system(pause_button::pause_button_text_system.system())
+                .add_system(pause_button::snap_button_system.system())
+                .add_system(pause_button::snap_button_text_system.system())
+                .add_system(pause_button::fit_button_system.system())
+                .add_system(pause_button::animation_button_system.system())
+                .add_system(pause_button::animation_button_text_system.system())
+                .add_system(keyboard::help_overlay_system.system());
+        }
+        app.add_system_to_stage(stage::INPUT, systems::animate_system.system());
+        if self.input {
+            app.add_system_to_stage(stage::INPUT, keyboard::keyboard_system.system())
+                .add_system_to_stage(stage::INPUT, keyboard::keyboard_theta_system.system())
+                .add_system_to_stage(stage::INPUT, keyboard::value_entry_system.system())
+                .add_system_to_stage(stage::INPUT, keyboard::mode_keyboard_system.system())
+                .add_system_to_stage(stage::INPUT, systems::mouse_click_system.system())
+                .add_system_to_stage(stage::INPUT, camera::zoom_system.system())
+                .add_system_to_stage(stage::INPUT, camera::pan_system.system());
+        }
+
+        app.add_system(systems::resize_circle_system.system())
+            .add_system(systems::axes_system.system())
+            .add_system(systems::grid_system.system())
+            .add_system(systems::geometry_system.system())
+            .add_system(systems::radius_line_system.system())
+            .add_system(systems::circle_system.system())
+            .add_system(systems::angle_arc_system.system())
+            .add_system(systems::cos_line_system.system())
+            .add_system(systems::sin_line_system.system())
+            .add_system(systems::unbounded_lines_system.system())
+            .add_system(systems::archaic_lines_system.system())
+            .add_system(systems::wave_graph_system.system())
+            .add_system(systems::wave_marker_system.system())
+            .add_system(systems::snap_cue_system.system())
+            .add_system(systems::inverse_system.system())
+            .add_system(systems::complex_system.system())
+            .add_system(systems::hyperbola_system.system())
+            .add_system(systems::canvas_labels_system.system())
+            .add_system(systems::function_visibility_system.system())
+            .add_system(systems::theme_system.system())
+            .add_system_to_stage(bevy::app::stage::POST_UPDATE, systems::view_system.system());
+
+        if self.ui {
+            app.add_system(systems::theta_text_system.system())
+                .add_system(systems::readout_system.system())
+                .add_system(systems::inverse_text_system.system())
+                .add_system(systems::complex_text_system.system())
+                .add_system(systems::hyperbolic_text_system.system());
+        }
+    }
+}
+
+fn setup(commands: &mut Commands) {
+    commands.spawn(Camera2dBundle::default()).with(MainCamera);
+}
+
+fn ui_setup(commands: &mut Commands) {
+    commands.spawn(CameraUiBundle::default());
+}
@@ -0,0 +1,71 @@
+use crate::plugin::systems::camera_home;
+use crate::{CameraView, Placement, Point};
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::input::touch::Touches;
+use bevy::prelude::*;
+
+/// Roughly how many pixels a touchpad scrolls for one notch of a mouse wheel.
+const PIXELS_PER_LINE: f32 = 40.;
+
+/// Zooms with the mouse wheel or a two-finger pinch, about the cursor or the
+/// middle of the pinch.
+pub fn zoom_system(
+    mut view: ResMut<CameraView>,
+    mut wheel_reader: Local<EventReader<MouseWheel>>,
+    wheel_events: Res<Events<MouseWheel>>,
+    touches: Res<Touches>,
+    placement: Res<Placement>,
+    windows: Res<Windows>,
+) {
+    let window = windows.get_primary().unwrap();
+    let home = camera_home(window, &placement);
+    let centre = Vec2::new(window.width() / 2., window.height() / 2.);
+    let cursor = window.cursor_position().map_or(Point::ORIGIN, |pos| {
+        Point::new(pos.x - centre.x, pos.y - centre.y)
+    });
+
+    for event in wheel_reader.iter(&wheel_events) {
+        let lines = match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_LINE,
+        };
+        if lines != 0. {
+            view.zoom_by(CameraView::ZOOM_STEP.powf(lines), home, cursor);
+        }
+    }
+
+    let touches: Vec<_> = touches.iter().collect();
+    if let [a, b] = touches[..] {
+        let previous = (a.previous_position() - b.previous_position()).length();
+        let current = (a.position() - b.position()).length();
+        if previous > 0. && current != previous {
+            // touch positions are measured down from the top of the window
+            let middle = (a.position() + b.position()) / 2.;
+            let anchor = Point::new(middle.x - centre.x, centre.y - middle.y);
+            view.zoom_by(current / previous, home, anchor);
+        }
+    }
+}
+
+/// Pans with the right or middle mouse button, leaving the left one to drag θ.
+pub fn pan_system(
+    mut last_cursor: Local<Option<Vec2>>,
+    mut view: ResMut<CameraView>,
+    windows: Res<Windows>,
+    mouse_button_input: Res<Input<MouseButton>>,
+) {
+    let window = windows.get_primary().unwrap();
+    let cursor = window.cursor_position();
+    let panning = mouse_button_input.pressed(MouseButton::Right)
+        || mouse_button_input.pressed(MouseButton::Middle);
+
+    if panning {
+        if let (Some(last), Some(cursor)) = (*last_cursor, cursor) {
+            let delta = cursor - last;
+            if delta != Vec2::zero() {
+                view.pan_by(Point::new(delta.x, delta.y));
+            }
+        }
+    }
+    *last_cursor = if panning { cursor } else { None };
+}
@@ -0,0 +1,36 @@
+use crate::palette::{self, Rgba};
+use crate::Function;
+use bevy::render::prelude::Color;
+
+pub use crate::scene::{
+    arc_radius, line_width, LABEL_FONT_SIZE, TICK_SIZE, WAVE_FUNCTIONS, WAVE_SAMPLES,
+};
+
+/// The palette is shared with the snapshot renderer, so a snapshot looks
+/// like the window.
+pub const fn color(c: Rgba) -> Color {
+    Color::rgba_linear(c.r, c.g, c.b, c.a)
+}
+
+pub const COS_COLOR: Color = color(palette::COS);
+pub const SIN_COLOR: Color = color(palette::SIN);
+pub const TAN_COLOR: Color = color(palette::TAN);
+pub const COT_COLOR: Color = color(palette::COT);
+pub const SEC_COLOR: Color = color(palette::SEC);
+pub const CSC_COLOR: Color = color(palette::CSC);
+pub const VERSIN_COLOR: Color = color(palette::VERSIN);
+pub const COVERSIN_COLOR: Color = color(palette::COVERSIN);
+pub const EXSEC_COLOR: Color = color(palette::EXSEC);
+pub const EXCSC_COLOR: Color = color(palette::EXCSC);
+pub const HAVERSIN_COLOR: Color = color(palette::HAVERSIN);
+
+pub fn function_color(function: Function) -> Color {
+    color(palette::function_color(function))
+}
+
+pub const HELP_TEXT: &str = r#"
+It's often helpful to think of cosine as width,
+sine as height, and tangent as slope.
+Scroll to zoom, drag with the right mouse button to pan.
+Press H to see the keyboard controls.
+"#;
@@ -0,0 +1,216 @@
+use crate::{Bounds, Function, HyperbolicFunction, InverseFunction, TableFormat, ValueEntry};
+use std::collections::HashSet;
+
+pub struct CosLine;
+pub struct SinLine;
+pub struct RadiusLine;
+/// Versine, coversine, exsecant, excosecant and haversine lines.
+pub struct ArchaicLine;
+pub struct Circle;
+/// The x and y axes through the centre of the circle, drawn across the viewport.
+pub struct XAxis;
+pub struct YAxis;
+/// One of the layers of the coordinate grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridShape {
+    MinorLines,
+    MajorLines,
+    Ticks,
+}
+/// A number on the grid's axes or an angle around the circle.
+pub struct GridLabel;
+pub struct MainCamera;
+/// The panel listing the key bindings.
+pub struct HelpOverlay;
+/// The filled wedge between the positive x axis and the radius line.
+pub struct AngleArc;
+/// The "θ" next to the angle arc.
+pub struct ThetaLabel;
+/// The name of a function, next to its line on the canvas.
+pub struct FunctionLabel(pub Function);
+/// Ring around the point on the circle while θ is snapped to an angle.
+pub struct SnapCue;
+/// The range of the inverse function's principal value, shaded on the circle.
+pub struct InverseRange;
+/// The line the inverse function's value is picked on.
+pub struct InverseGuide;
+/// The draggable point on the guide at the inverse function's value.
+pub struct InverseHandle;
+/// The radius lines to every angle with the inverse function's value.
+pub struct InverseAngles;
+/// "Re" or "Im" at the end of an axis, shown on the complex plane.
+pub struct AxisName;
+/// The dashed line to the factor and the solid one to the product, on the
+/// complex plane.
+pub struct ComplexLines;
+/// A dot on the complex plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComplexPoint {
+    /// The number `e^{iθ}` is multiplied by, which can be dragged.
+    Factor,
+    /// `e^{iθ}` times the factor.
+    Product,
+}
+/// The polygon through the roots of unity.
+pub struct RootsPolygon;
+/// Only shown with the unit circle, i.e. hidden while the hyperbola is shown.
+pub struct CircleOnly;
+/// Only shown with the unit hyperbola.
+pub struct HyperbolaOnly;
+/// One of the shapes of the unit hyperbola.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HyperbolaShape {
+    Curve,
+    /// The dashed lines y = ±x.
+    Asymptotes,
+    /// The shaded area, half the hyperbolic angle.
+    Area,
+    RadiusLine,
+}
+/// The line of a hyperbolic function on the hyperbola.
+pub struct HyperbolicLine(pub HyperbolicFunction);
+
+/// The θ = 0 axis of the wave graph.
+pub struct WaveAxis;
+/// The graph of a function against θ.
+pub struct WaveCurve(pub Function);
+/// The current θ on a function's graph.
+pub struct WaveMarker(pub Function);
+/// Dashed line from the point on the circle to the sine graph.
+pub struct WaveConnector;
+/// Dashed guides through the asymptotes of a function's graph.
+pub struct WaveAsymptotes(pub Function);
+
+pub struct CosText;
+pub struct SinText;
+pub struct TanText;
+pub struct CotText;
+pub struct SecText;
+pub struct CscText;
+pub struct ThetaText;
+pub struct ArchaicText;
+pub struct InverseText;
+pub struct ComplexText;
+pub struct HyperbolicText;
+
+/// Which function a line or readout row belongs to, used to show and hide them.
+pub struct FunctionLine(pub Function);
+pub struct FunctionText(pub Function);
+
+/// The angle being shown, in radians.
+pub struct Theta(pub f32);
+/// What moved θ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThetaSource {
+    Animation,
+    Mouse,
+    Keyboard,
+    /// A host app, or anything else that isn't part of the visualization.
+    Script,
+}
+
+/// Asks for θ to be moved, in radians. Every change to θ goes through this,
+/// see [`apply_theta_system`](super::systems::apply_theta_system) for which
+/// request wins when there are several in a frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SetTheta {
+    pub theta: f32,
+    pub source: ThetaSource,
+}
+
+impl SetTheta {
+    /// A request from outside the visualization.
+    pub fn script(theta: f32) -> Self {
+        Self {
+            theta,
+            source: ThetaSource::Script,
+        }
+    }
+}
+
+/// Sent whenever θ has actually moved, in radians.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThetaChanged {
+    pub old: f32,
+    pub new: f32,
+    pub source: ThetaSource,
+}
+pub struct Radius(pub f32);
+/// The inverse function being explored, if any, the value it's applied to and
+/// the value being typed in.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Inverse {
+    pub function: Option<InverseFunction>,
+    pub value: f32,
+    pub entry: ValueEntry,
+}
+
+pub struct Paused(pub bool);
+pub struct PauseButtonHovered(pub bool);
+pub struct HelpVisible(pub bool);
+pub struct GridVisible(pub bool);
+/// Whether the unit hyperbola is shown instead of the unit circle.
+pub struct HyperbolicView(pub bool);
+/// The kinds of file a snapshot of the diagram can be saved as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageFormat {
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Svg => "svg",
+        }
+    }
+}
+
+/// Set to save a snapshot of the diagram at the end of the frame.
+pub struct SnapshotRequested(pub Option<ImageFormat>);
+/// Set to save a table of the values at the end of the frame.
+pub struct TableRequested(pub Option<TableFormat>);
+/// The part of the world that is on screen.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct Viewport(pub Bounds);
+
+pub struct ShownFunctions(pub HashSet<Function>);
+
+impl Default for ShownFunctions {
+    fn default() -> Self {
+        ShownFunctions(Function::BASIC.iter().copied().collect())
+    }
+}
+
+impl ShownFunctions {
+    pub fn contains(&self, function: Function) -> bool {
+        self.0.contains(&function)
+    }
+
+    /// The shown functions in the order of [`Function::ALL`].
+    pub fn ordered(&self) -> Vec<Function> {
+        Function::ALL
+            .iter()
+            .copied()
+            .filter(|function| self.contains(*function))
+            .collect()
+    }
+
+    pub fn toggle(&mut self, function: Function) {
+        if !self.0.remove(&function) {
+            self.0.insert(function);
+        }
+    }
+
+    /// Hides the whole group if any of it is shown, otherwise shows all of it.
+    pub fn toggle_group(&mut self, group: &[Function]) {
+        if group.iter().any(|function| self.contains(*function)) {
+            for function in group {
+                self.0.remove(function);
+            }
+        } else {
+            self.0.extend(group.iter().copied());
+        }
+    }
+}
@@ -0,0 +1,548 @@
+use crate::angle::step_angle;
+use crate::plugin::entity::{
+    GridVisible, HelpOverlay, HelpVisible, HyperbolicView, ImageFormat, Inverse, Paused, SetTheta,
+    ShownFunctions, SnapshotRequested, TableRequested, Theta, ThetaSource,
+};
+use crate::plugin::systems::camera_home;
+use crate::{
+    AngleUnit, AnimationSettings, CameraView, ComplexPlane, Function, InverseFunction, Placement,
+    Point, Snapping, TableFormat, Theme, ValueDisplay,
+};
+use bevy::prelude::*;
+use std::f32::consts::PI;
+
+const FINE_STEP: f32 = PI / 180.;
+const COARSE_STEP: f32 = PI / 12.;
+
+/// The keys that type a value in while an inverse function is being
+/// explored, and so don't do what they're bound to then.
+const ENTRY_KEYS: [KeyCode; 28] = [
+    KeyCode::Key0,
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+    KeyCode::Numpad0,
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+    KeyCode::Minus,
+    KeyCode::Subtract,
+    KeyCode::Period,
+    KeyCode::Decimal,
+    KeyCode::Back,
+    KeyCode::Return,
+    KeyCode::NumpadEnter,
+    KeyCode::Escape,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    StepForward,
+    StepBackward,
+    CoarseStepForward,
+    CoarseStepBackward,
+    TogglePause,
+    SpeedUp,
+    SlowDown,
+    Reverse,
+    NextSweep,
+    NextEasing,
+    Reset,
+    ZoomIn,
+    ZoomOut,
+    FitCircle,
+    ToggleGrid,
+    ToggleFunction(Function),
+    ToggleArchaic,
+    NextAngleUnit,
+    NextValueDisplay,
+    ToggleSnapping,
+    NextInverse,
+    ToggleComplexPlane,
+    ToggleFactor,
+    NextRoots,
+    ToggleHyperbola,
+    NextTheme,
+    Snapshot(ImageFormat),
+    ExportTable(TableFormat),
+    ToggleHelp,
+}
+
+impl Action {
+    pub fn description(self) -> String {
+        match self {
+            Action::StepForward => "step θ forward 1°".into(),
+            Action::StepBackward => "step θ back 1°".into(),
+            Action::CoarseStepForward => "step θ forward 15°".into(),
+            Action::CoarseStepBackward => "step θ back 15°".into(),
+            Action::TogglePause => "pause / resume".into(),
+            Action::SpeedUp => "speed up".into(),
+            Action::SlowDown => "slow down".into(),
+            Action::Reverse => "reverse direction".into(),
+            Action::NextSweep => "full circle or back and forth".into(),
+            Action::NextEasing => "next easing".into(),
+            Action::Reset => "reset θ to 0".into(),
+            Action::ZoomIn => "zoom in".into(),
+            Action::ZoomOut => "zoom out".into(),
+            Action::FitCircle => "fit the circle on screen".into(),
+            Action::ToggleGrid => "show / hide the grid".into(),
+            Action::ToggleFunction(function) => format!("show / hide {}", function.name()),
+            Action::ToggleArchaic => "show / hide versin, coversin, exsec, excsc, hav".into(),
+            Action::NextAngleUnit => "next angle unit".into(),
+            Action::NextValueDisplay => "exact values, decimals or both".into(),
+            Action::ToggleSnapping => "snap to angles while dragging".into(),
+            Action::NextInverse => "asin, acos, atan, acot or none, then type a value".into(),
+            Action::ToggleComplexPlane => "show the circle as the complex plane".into(),
+            Action::ToggleFactor => "multiply e^{iθ} by a draggable w".into(),
+            Action::NextRoots => "one more root of unity, or none".into(),
+            Action::ToggleHyperbola => "unit circle or unit hyperbola".into(),
+            Action::NextTheme => "dark or light theme".into(),
+            Action::Snapshot(ImageFormat::Png) => "save a PNG snapshot".into(),
+            Action::Snapshot(ImageFormat::Svg) => "save an SVG snapshot".into(),
+            Action::ExportTable(TableFormat::Csv) => "save a table of values as CSV".into(),
+            Action::ExportTable(TableFormat::Json) => "save a table of values as JSON".into(),
+            Action::ToggleHelp => "show / hide this help".into(),
+        }
+    }
+}
+
+/// Every key binding in one place, so they can be remapped, e.g. with
+/// [`TrigVisualizationPlugin::with_key`](crate::TrigVisualizationPlugin::with_key).
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    bindings: Vec<(KeyCode, Action)>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        let mut bindings = vec![
+            (KeyCode::Right, Action::StepForward),
+            (KeyCode::Left, Action::StepBackward),
+            (KeyCode::Up, Action::CoarseStepForward),
+            (KeyCode::Down, Action::CoarseStepBackward),
+            (KeyCode::Space, Action::TogglePause),
+            (KeyCode::Equals, Action::SpeedUp),
+            (KeyCode::Plus, Action::SpeedUp),
+            (KeyCode::Minus, Action::SlowDown),
+            (KeyCode::R, Action::Reverse),
+            (KeyCode::P, Action::NextSweep),
+            (KeyCode::L, Action::NextEasing),
+            (KeyCode::Home, Action::Reset),
+            (KeyCode::PageUp, Action::ZoomIn),
+            (KeyCode::PageDown, Action::ZoomOut),
+            (KeyCode::F, Action::FitCircle),
+            (KeyCode::G, Action::ToggleGrid),
+        ];
+        let function_keys = [
+            KeyCode::Key1,
+            KeyCode::Key2,
+            KeyCode::Key3,
+            KeyCode::Key4,
+            KeyCode::Key5,
+            KeyCode::Key6,
+        ];
+        for (key, function) in function_keys.iter().zip(Function::BASIC.iter()) {
+            bindings.push((*key, Action::ToggleFunction(*function)));
+        }
+        bindings.extend(vec![
+            (KeyCode::A, Action::ToggleArchaic),
+            (KeyCode::U, Action::NextAngleUnit),
+            (KeyCode::E, Action::NextValueDisplay),
+            (KeyCode::S, Action::ToggleSnapping),
+            (KeyCode::I, Action::NextInverse),
+            (KeyCode::C, Action::ToggleComplexPlane),
+            (KeyCode::M, Action::ToggleFactor),
+            (KeyCode::N, Action::NextRoots),
+            (KeyCode::Tab, Action::ToggleHyperbola),
+            (KeyCode::D, Action::NextTheme),
+            (KeyCode::H, Action::ToggleHelp),
+            (KeyCode::F1, Action::ToggleHelp),
+        ]);
+        // there's nowhere to save a file to in the browser
+        #[cfg(not(target_arch = "wasm32"))]
+        bindings.extend(vec![
+            (KeyCode::F12, Action::Snapshot(ImageFormat::Png)),
+            (KeyCode::V, Action::Snapshot(ImageFormat::Svg)),
+            (KeyCode::T, Action::ExportTable(TableFormat::Csv)),
+            (KeyCode::J, Action::ExportTable(TableFormat::Json)),
+        ]);
+        KeyMap { bindings }
+    }
+}
+
+impl KeyMap {
+    /// Binds `key` to `action`, replacing whatever `key` did before.
+    pub fn bind(&mut self, key: KeyCode, action: Action) {
+        self.unbind(key);
+        self.bindings.push((key, action));
+    }
+
+    pub fn unbind(&mut self, key: KeyCode) {
+        self.bindings.retain(|(bound, _)| *bound != key);
+    }
+
+    /// The actions whose keys were pressed this frame, leaving out the keys
+    /// that type a value while `typing`.
+    pub fn just_pressed<'a>(
+        &'a self,
+        input: &'a Input<KeyCode>,
+        typing: bool,
+    ) -> impl Iterator<Item = Action> + 'a {
+        self.bindings
+            .iter()
+            .filter(move |(key, _)| input.just_pressed(*key))
+            .filter(move |(key, _)| !typing || !ENTRY_KEYS.contains(key))
+            .map(|(_, action)| *action)
+    }
+
+    /// One line per action listing its keys, in the order they were bound.
+    pub fn help(&self) -> String {
+        let mut actions: Vec<Action> = Vec::new();
+        for (_, action) in &self.bindings {
+            if !actions.contains(action) {
+                actions.push(*action);
+            }
+        }
+
+        let lines: Vec<(String, String)> = actions
+            .iter()
+            .map(|action| {
+                let keys: Vec<String> = self
+                    .bindings
+                    .iter()
+                    .filter(|(_, bound)| bound == action)
+                    .map(|(key, _)| key_name(*key))
+                    .collect();
+                (keys.join(" / "), action.description())
+            })
+            .collect();
+        let width = lines.iter().map(|(keys, _)| keys.chars().count()).max();
+        lines
+            .iter()
+            .map(|(keys, description)| {
+                format!(
+                    "{:<width$}  {}",
+                    keys,
+                    description,
+                    width = width.unwrap_or(0)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn key_name(key: KeyCode) -> String {
+    match key {
+        KeyCode::Key1 => "1".into(),
+        KeyCode::Key2 => "2".into(),
+        KeyCode::Key3 => "3".into(),
+        KeyCode::Key4 => "4".into(),
+        KeyCode::Key5 => "5".into(),
+        KeyCode::Key6 => "6".into(),
+        KeyCode::Key7 => "7".into(),
+        KeyCode::Key8 => "8".into(),
+        KeyCode::Key9 => "9".into(),
+        KeyCode::Key0 => "0".into(),
+        KeyCode::Right => "→".into(),
+        KeyCode::Left => "←".into(),
+        KeyCode::Up => "↑".into(),
+        KeyCode::Down => "↓".into(),
+        KeyCode::Equals => "=".into(),
+        KeyCode::Plus => "+".into(),
+        KeyCode::Minus => "-".into(),
+        key => format!("{:?}", key),
+    }
+}
+
+/// Steps θ, puts it back to 0 or moves it to the principal value of the next
+/// inverse function, through the same requests as the mouse and the animation.
+pub fn keyboard_theta_system(
+    keymap: Res<KeyMap>,
+    keyboard_input: Res<Input<KeyCode>>,
+    theta: Res<Theta>,
+    mut inverse: ResMut<Inverse>,
+    mut hyperbolic: ResMut<HyperbolicView>,
+    mut set_theta: ResMut<Events<SetTheta>>,
+) {
+    let typing = inverse.function.is_some();
+    for action in keymap.just_pressed(&keyboard_input, typing) {
+        let new_theta = match action {
+            Action::StepForward => step_angle(theta.0, FINE_STEP, 1.),
+            Action::StepBackward => step_angle(theta.0, FINE_STEP, -1.),
+            Action::CoarseStepForward => step_angle(theta.0, COARSE_STEP, 1.),
+            Action::CoarseStepBackward => step_angle(theta.0, COARSE_STEP, -1.),
+            Action::Reset => 0.,
+            Action::NextInverse => {
+                inverse.function = InverseFunction::next(inverse.function);
+                inverse.entry.clear();
+                if inverse.function.is_some() {
+                    hyperbolic.0 = false;
+                }
+                // start from the value θ has, e.g. cot 0 has none
+                match inverse.function {
+                    Some(function) => {
+                        inverse.value = function.value_of(theta.0).unwrap_or(0.);
+                        match function.principal(inverse.value) {
+                            Some(principal) => principal,
+                            None => continue,
+                        }
+                    }
+                    None => continue,
+                }
+            }
+            _ => continue,
+        };
+        set_theta.send(SetTheta {
+            theta: new_theta,
+            source: ThetaSource::Keyboard,
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn keyboard_system(
+    keymap: Res<KeyMap>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut paused: ResMut<Paused>,
+    mut animation: ResMut<AnimationSettings>,
+    mut shown: ResMut<ShownFunctions>,
+    mut unit: ResMut<AngleUnit>,
+    mut display: ResMut<ValueDisplay>,
+    mut snapping: ResMut<Snapping>,
+    mut help: ResMut<HelpVisible>,
+    mut view: ResMut<CameraView>,
+    mut grid_visible: ResMut<GridVisible>,
+    mut snapshot: ResMut<SnapshotRequested>,
+    mut table: ResMut<TableRequested>,
+    inverse: Res<Inverse>,
+    placement: Res<Placement>,
+    windows: Res<Windows>,
+) {
+    let home = camera_home(windows.get_primary().unwrap(), &placement);
+    let typing = inverse.function.is_some();
+    for action in keymap.just_pressed(&keyboard_input, typing) {
+        match action {
+            // see `keyboard_theta_system`
+            Action::StepForward
+            | Action::StepBackward
+            | Action::CoarseStepForward
+            | Action::CoarseStepBackward
+            | Action::Reset
+            | Action::NextInverse => {}
+            // see `mode_keyboard_system`
+            Action::ToggleComplexPlane
+            | Action::ToggleFactor
+            | Action::NextRoots
+            | Action::ToggleHyperbola
+            | Action::NextTheme => {}
+            Action::TogglePause => paused.0 = !paused.0,
+            Action::SpeedUp => animation.scale_speed(AnimationSettings::SPEED_STEP),
+            Action::SlowDown => animation.scale_speed(AnimationSettings::SPEED_STEP.recip()),
+            Action::Reverse => animation.direction = animation.direction.reversed(),
+            Action::NextSweep => animation.sweep = animation.sweep.next(),
+            Action::NextEasing => animation.easing = animation.easing.next(),
+            // zoom about the middle of the window
+            Action::ZoomIn => view.zoom_by(CameraView::ZOOM_STEP, home, Point::ORIGIN),
+            Action::ZoomOut => view.zoom_by(CameraView::ZOOM_STEP.recip(), home, Point::ORIGIN),
+            Action::FitCircle => view.fit(),
+            Action::ToggleGrid => grid_visible.0 = !grid_visible.0,
+            Action::ToggleFunction(function) => shown.toggle(function),
+            Action::ToggleArchaic => shown.toggle_group(&Function::ARCHAIC),
+            Action::NextAngleUnit => *unit = unit.next(),
+            Action::NextValueDisplay => *display = display.next(),
+            Action::ToggleSnapping => snapping.enabled = !snapping.enabled,
+            Action::Snapshot(format) => snapshot.0 = Some(format),
+            Action::ExportTable(format) => table.0 = Some(format),
+            Action::ToggleHelp => help.0 = !help.0,
+        }
+    }
+}
+
+/// Switches between the circle, the complex plane and the hyperbola. The
+/// complex plane's overlays turn the plane on along with them, and the
+/// hyperbola, which has no inverse functions or complex numbers drawn on it,
+/// turns both off. Also switches the theme, which `theme_system` redraws in.
+pub fn mode_keyboard_system(
+    keymap: Res<KeyMap>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut inverse: ResMut<Inverse>,
+    mut complex: ResMut<ComplexPlane>,
+    mut hyperbolic: ResMut<HyperbolicView>,
+    mut theme: ResMut<Theme>,
+) {
+    let typing = inverse.function.is_some();
+    for action in keymap.just_pressed(&keyboard_input, typing) {
+        match action {
+            Action::ToggleComplexPlane => complex.enabled = !complex.enabled,
+            Action::ToggleFactor => {
+                complex.toggle_factor();
+                complex.enabled |= complex.factor.is_some();
+            }
+            Action::NextRoots => {
+                complex.next_roots();
+                complex.enabled |= complex.roots.is_some();
+            }
+            Action::ToggleHyperbola => {
+                hyperbolic.0 = !hyperbolic.0;
+                if hyperbolic.0 {
+                    complex.enabled = false;
+                    inverse.function = None;
+                    inverse.entry.clear();
+                }
+            }
+            Action::NextTheme => {
+                *theme = theme.next();
+                continue;
+            }
+            _ => continue,
+        }
+        if complex.enabled {
+            hyperbolic.0 = false;
+        }
+    }
+}
+
+/// Types in a value for the inverse function, which is applied on Enter.
+/// Backspace takes back a character and Escape starts over.
+pub fn value_entry_system(
+    mut reader: Local<EventReader<ReceivedCharacter>>,
+    characters: Res<Events<ReceivedCharacter>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut inverse: ResMut<Inverse>,
+    mut set_theta: ResMut<Events<SetTheta>>,
+) {
+    // read the characters even when not typing, so that they don't pile up
+    // for the next time an inverse function is picked
+    let typed: Vec<char> = reader.iter(&characters).map(|event| event.char).collect();
+    let function = match inverse.function {
+        Some(function) => function,
+        None => return,
+    };
+
+    for c in typed {
+        inverse.entry.push(c);
+    }
+    if keyboard_input.just_pressed(KeyCode::Back) {
+        inverse.entry.pop();
+    }
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        inverse.entry.clear();
+    }
+    if keyboard_input.just_pressed(KeyCode::Return)
+        || keyboard_input.just_pressed(KeyCode::NumpadEnter)
+    {
+        if let Some(value) = inverse.entry.value() {
+            inverse.value = value;
+            if let Some(principal) = function.principal(value) {
+                set_theta.send(SetTheta {
+                    theta: principal,
+                    source: ThetaSource::Keyboard,
+                });
+            }
+        }
+        inverse.entry.clear();
+    }
+}
+
+/// Shows or hides the help overlay, listing the current key bindings.
+pub fn help_overlay_system(
+    keymap: Res<KeyMap>,
+    help: Res<HelpVisible>,
+    mut overlay: Query<(&mut Style, &Children), With<HelpOverlay>>,
+    mut text_query: Query<&mut Text>,
+) {
+    let display = if help.0 { Display::Flex } else { Display::None };
+    for (mut style, children) in overlay.iter_mut() {
+        if style.display != display {
+            style.display = display;
+        }
+        if !help.0 {
+            continue;
+        }
+        let value = keymap.help();
+        let mut text = text_query.get_mut(children[0]).unwrap();
+        if text.value != value {
+            text.value = value;
+        }
+    }
+}
+
+pub fn setup(
+    commands: &mut Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    asset_server: Res<AssetServer>,
+) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    right: Val::Px(10.),
+                    top: Val::Px(62.),
+                    ..Default::default()
+                },
+                padding: Rect::all(Val::Px(10.)),
+                ..Default::default()
+            },
+            material: materials.add(Color::rgba_linear(0.02, 0.02, 0.02, 0.85).into()),
+            visible: Visible {
+                is_transparent: true,
+                is_visible: true,
+            },
+            ..Default::default()
+        })
+        .with(HelpOverlay)
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text {
+                    value: String::new(),
+                    font: asset_server.load("fonts/FiraMono-Regular.ttf"),
+                    style: TextStyle {
+                        font_size: 16.0,
+                        color: Color::rgb_linear(0.7, 0.7, 0.7),
+                        ..Default::default()
+                    },
+                },
+                ..Default::default()
+            });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pressed(keymap: &KeyMap, key: KeyCode) -> Vec<Action> {
+        let mut input = Input::default();
+        input.press(key);
+        keymap.just_pressed(&input, false).collect()
+    }
+
+    #[test]
+    fn rebound_keys_fire_their_new_action() {
+        let mut keymap = KeyMap::default();
+        assert_eq!(pressed(&keymap, KeyCode::Space), vec![Action::TogglePause]);
+
+        keymap.unbind(KeyCode::Space);
+        keymap.bind(KeyCode::K, Action::TogglePause);
+        keymap.bind(KeyCode::G, Action::Reverse);
+        assert_eq!(pressed(&keymap, KeyCode::K), vec![Action::TogglePause]);
+        assert!(pressed(&keymap, KeyCode::Space).is_empty());
+        // binding a key replaces what it did before
+        assert_eq!(pressed(&keymap, KeyCode::G), vec![Action::Reverse]);
+    }
+}
@@ -0,0 +1,380 @@
+use crate::plugin::entity::{PauseButtonHovered, Paused};
+use crate::{AnimationSettings, CameraView, Snapping};
+use bevy::prelude::*;
+
+pub struct PauseButton;
+pub struct SnapButton;
+/// Undoes any zooming and panning.
+pub struct FitButton;
+
+/// The animation controls along the bottom of the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationButton {
+    Slower,
+    Faster,
+    Reverse,
+    Sweep,
+    Easing,
+}
+
+impl AnimationButton {
+    /// Left to right.
+    const ALL: [AnimationButton; 5] = [
+        AnimationButton::Slower,
+        AnimationButton::Faster,
+        AnimationButton::Reverse,
+        AnimationButton::Sweep,
+        AnimationButton::Easing,
+    ];
+
+    fn width(self) -> f32 {
+        match self {
+            AnimationButton::Slower | AnimationButton::Faster => 80.,
+            AnimationButton::Reverse => 100.,
+            AnimationButton::Sweep => 150.,
+            AnimationButton::Easing => 190.,
+        }
+    }
+
+    fn label(self, animation: &AnimationSettings) -> String {
+        match self {
+            AnimationButton::Slower => "Slower".into(),
+            AnimationButton::Faster => "Faster".into(),
+            AnimationButton::Reverse => format!("{:?}", animation.direction),
+            AnimationButton::Sweep => animation.sweep.name(),
+            AnimationButton::Easing => animation.easing.name().into(),
+        }
+    }
+
+    fn apply(self, animation: &mut AnimationSettings) {
+        match self {
+            AnimationButton::Slower => animation.scale_speed(AnimationSettings::SPEED_STEP.recip()),
+            AnimationButton::Faster => animation.scale_speed(AnimationSettings::SPEED_STEP),
+            AnimationButton::Reverse => animation.direction = animation.direction.reversed(),
+            AnimationButton::Sweep => animation.sweep = animation.sweep.next(),
+            AnimationButton::Easing => animation.easing = animation.easing.next(),
+        }
+    }
+}
+
+pub struct ButtonMaterials {
+    normal: Handle<ColorMaterial>,
+    hovered: Handle<ColorMaterial>,
+    pressed: Handle<ColorMaterial>,
+}
+
+impl FromResources for ButtonMaterials {
+    fn from_resources(resources: &Resources) -> Self {
+        let mut materials = resources.get_mut::<Assets<ColorMaterial>>().unwrap();
+        ButtonMaterials {
+            normal: materials.add(Color::rgb(0.25, 0.25, 0.25).into()),
+            hovered: materials.add(Color::rgb(0.35, 0.35, 0.35).into()),
+            pressed: materials.add(Color::rgb(0.45, 0.45, 0.45).into()),
+        }
+    }
+}
+
+/// Keeps the mouse from dragging θ around while it is over any of the buttons.
+pub fn button_hover_system(
+    mut pause_button_hovered: ResMut<PauseButtonHovered>,
+    interaction_query: Query<&Interaction, With<Button>>,
+) {
+    let hovered = interaction_query
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+    if pause_button_hovered.0 != hovered {
+        pause_button_hovered.0 = hovered;
+    }
+}
+
+pub fn pause_button_system(
+    mut paused: ResMut<Paused>,
+    button_materials: Res<ButtonMaterials>,
+    mut interaction_query: Query<
+        (&Interaction, &mut Handle<ColorMaterial>),
+        (Mutated<Interaction>, With<PauseButton>),
+    >,
+) {
+    for (interaction, mut material) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *material = button_materials.pressed.clone();
+                paused.0 = !paused.0;
+            }
+            Interaction::Hovered => {
+                *material = button_materials.hovered.clone();
+            }
+            Interaction::None => {
+                *material = button_materials.normal.clone();
+            }
+        }
+    }
+}
+
+/// Pausing can also be toggled from the keyboard, so the label follows the
+/// resource rather than the clicks.
+pub fn pause_button_text_system(
+    paused: Res<Paused>,
+    button_query: Query<&Children, With<PauseButton>>,
+    mut text_query: Query<&mut Text>,
+) {
+    let value = if paused.0 { "Resume" } else { "Pause" };
+    for children in button_query.iter() {
+        let mut text = text_query.get_mut(children[0]).unwrap();
+        if text.value != value {
+            text.value = value.into();
+        }
+    }
+}
+
+pub fn snap_button_system(
+    mut snapping: ResMut<Snapping>,
+    button_materials: Res<ButtonMaterials>,
+    mut interaction_query: Query<
+        (&Interaction, &mut Handle<ColorMaterial>),
+        (Mutated<Interaction>, With<SnapButton>),
+    >,
+) {
+    for (interaction, mut material) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *material = button_materials.pressed.clone();
+                snapping.enabled = !snapping.enabled;
+            }
+            Interaction::Hovered => {
+                *material = button_materials.hovered.clone();
+            }
+            Interaction::None => {
+                *material = button_materials.normal.clone();
+            }
+        }
+    }
+}
+
+pub fn fit_button_system(
+    mut view: ResMut<CameraView>,
+    button_materials: Res<ButtonMaterials>,
+    mut interaction_query: Query<
+        (&Interaction, &mut Handle<ColorMaterial>),
+        (Mutated<Interaction>, With<FitButton>),
+    >,
+) {
+    for (interaction, mut material) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *material = button_materials.pressed.clone();
+                view.fit();
+            }
+            Interaction::Hovered => {
+                *material = button_materials.hovered.clone();
+            }
+            Interaction::None => {
+                *material = button_materials.normal.clone();
+            }
+        }
+    }
+}
+
+/// Snapping can also be toggled from the keyboard, so the label follows the
+/// resource rather than the clicks.
+pub fn snap_button_text_system(
+    snapping: Res<Snapping>,
+    button_query: Query<&Children, With<SnapButton>>,
+    mut text_query: Query<&mut Text>,
+) {
+    let value = if snapping.enabled {
+        "Snap on"
+    } else {
+        "Snap off"
+    };
+    for children in button_query.iter() {
+        let mut text = text_query.get_mut(children[0]).unwrap();
+        if text.value != value {
+            text.value = value.into();
+        }
+    }
+}
+
+pub fn animation_button_system(
+    mut animation: ResMut<AnimationSettings>,
+    button_materials: Res<ButtonMaterials>,
+    mut interaction_query: Query<
+        (&Interaction, &mut Handle<ColorMaterial>, &AnimationButton),
+        Mutated<Interaction>,
+    >,
+) {
+    for (interaction, mut material, button) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *material = button_materials.pressed.clone();
+                button.apply(&mut animation);
+            }
+            Interaction::Hovered => {
+                *material = button_materials.hovered.clone();
+            }
+            Interaction::None => {
+                *material = button_materials.normal.clone();
+            }
+        }
+    }
+}
+
+/// The animation settings also change from the keyboard and when a ping-pong
+/// sweep bounces, so the labels follow the resource.
+pub fn animation_button_text_system(
+    animation: Res<AnimationSettings>,
+    button_query: Query<(&AnimationButton, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (button, children) in button_query.iter() {
+        let value = button.label(&animation);
+        let mut text = text_query.get_mut(children[0]).unwrap();
+        if text.value != value {
+            text.value = value;
+        }
+    }
+}
+
+pub fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    button_materials: Res<ButtonMaterials>,
+) {
+    commands
+        .spawn(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Px(100.0), Val::Px(42.0)),
+                // center button
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    right: Val::Px(10.),
+                    top: Val::Px(10.),
+                    ..Default::default()
+                },
+                // horizontally center child text
+                justify_content: JustifyContent::Center,
+                // vertically center child text
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: button_materials.normal.clone(),
+            ..Default::default()
+        })
+        .with(PauseButton)
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text {
+                    value: "Pause".into(),
+                    font: asset_server.load("fonts/FiraSans-Regular.ttf"),
+                    style: TextStyle {
+                        font_size: 24.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                        ..Default::default()
+                    },
+                },
+                ..Default::default()
+            });
+        })
+        .spawn(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Px(100.0), Val::Px(42.0)),
+                // to the left of the pause button
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    right: Val::Px(120.),
+                    top: Val::Px(10.),
+                    ..Default::default()
+                },
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: button_materials.normal.clone(),
+            ..Default::default()
+        })
+        .with(SnapButton)
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text {
+                    value: "Snap off".into(),
+                    font: asset_server.load("fonts/FiraSans-Regular.ttf"),
+                    style: TextStyle {
+                        font_size: 20.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                        ..Default::default()
+                    },
+                },
+                ..Default::default()
+            });
+        })
+        .spawn(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Px(100.0), Val::Px(42.0)),
+                // to the left of the snap button
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    right: Val::Px(230.),
+                    top: Val::Px(10.),
+                    ..Default::default()
+                },
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: button_materials.normal.clone(),
+            ..Default::default()
+        })
+        .with(FitButton)
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text {
+                    value: "Fit circle".into(),
+                    font: asset_server.load("fonts/FiraSans-Regular.ttf"),
+                    style: TextStyle {
+                        font_size: 20.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                        ..Default::default()
+                    },
+                },
+                ..Default::default()
+            });
+        });
+
+    let mut right = 10.;
+    for button in AnimationButton::ALL.iter().rev() {
+        commands
+            .spawn(ButtonBundle {
+                style: Style {
+                    size: Size::new(Val::Px(button.width()), Val::Px(36.0)),
+                    // in a row along the bottom right
+                    position_type: PositionType::Absolute,
+                    position: Rect {
+                        right: Val::Px(right),
+                        bottom: Val::Px(10.),
+                        ..Default::default()
+                    },
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                material: button_materials.normal.clone(),
+                ..Default::default()
+            })
+            .with(*button)
+            .with_children(|parent| {
+                parent.spawn(TextBundle {
+                    text: Text {
+                        value: String::new(),
+                        font: asset_server.load("fonts/FiraSans-Regular.ttf"),
+                        style: TextStyle {
+                            font_size: 18.0,
+                            color: Color::rgb(0.9, 0.9, 0.9),
+                            ..Default::default()
+                        },
+                    },
+                    ..Default::default()
+                });
+            });
+        right += button.width() + 10.;
+    }
+}
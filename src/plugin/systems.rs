@@ -0,0 +1,1733 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+
+use crate::annotation::{angle_arc, sector};
+use crate::complex::{axis_labels, roots_of_unity};
+use crate::hyperbolic::{asymptotes, hyperbola, parameter, parameter_at, theta_for};
+use crate::plugin::consts::*;
+use crate::plugin::entity::*;
+use crate::plugin::utils::{
+    fill, polygon_path, polylines_path, segments_path, stroke, stroke_behind, to_line, to_vec2,
+    SpawnLine, UpdateLine,
+};
+use crate::scene::{function_segments, CanvasLabels};
+use crate::wave::WAVE_LIMIT;
+use crate::{
+    format_value, AngleUnit, AnimationSettings, Bounds, CameraView, Complex, ComplexPlane,
+    Function, Grid, HyperbolicFunction, HyperbolicGeometry, InverseFunction, Layout, Placement,
+    Point, Segment, Snapping, Theme, TrigGeometry, ValueDisplay, WaveGraph,
+};
+
+/// One material per colour, shared by every line so that redrawing a line
+/// doesn't create a new asset.
+pub struct LineMaterials {
+    pub axis: Handle<ColorMaterial>,
+    pub grid_minor: Handle<ColorMaterial>,
+    pub grid_major: Handle<ColorMaterial>,
+    pub arc: Handle<ColorMaterial>,
+    pub highlight: Handle<ColorMaterial>,
+    pub circle: Handle<ColorMaterial>,
+    pub radius: Handle<ColorMaterial>,
+    pub cos: Handle<ColorMaterial>,
+    pub sin: Handle<ColorMaterial>,
+    pub tan: Handle<ColorMaterial>,
+    pub cot: Handle<ColorMaterial>,
+    pub sec: Handle<ColorMaterial>,
+    pub csc: Handle<ColorMaterial>,
+    pub versin: Handle<ColorMaterial>,
+    pub coversin: Handle<ColorMaterial>,
+    pub exsec: Handle<ColorMaterial>,
+    pub excsc: Handle<ColorMaterial>,
+    pub haversin: Handle<ColorMaterial>,
+}
+
+impl FromResources for LineMaterials {
+    fn from_resources(resources: &Resources) -> Self {
+        let mut materials = resources.get_mut::<Assets<ColorMaterial>>().unwrap();
+        let palette = resources.get::<Theme>().unwrap().palette();
+        LineMaterials {
+            axis: materials.add(color(palette.axis).into()),
+            grid_minor: materials.add(color(palette.grid_minor).into()),
+            grid_major: materials.add(color(palette.grid_major).into()),
+            arc: materials.add(color(palette.arc).into()),
+            highlight: materials.add(color(palette.highlight).into()),
+            circle: materials.add(color(palette.circle).into()),
+            radius: materials.add(color(palette.radius).into()),
+            cos: materials.add(COS_COLOR.into()),
+            sin: materials.add(SIN_COLOR.into()),
+            tan: materials.add(TAN_COLOR.into()),
+            cot: materials.add(COT_COLOR.into()),
+            sec: materials.add(SEC_COLOR.into()),
+            csc: materials.add(CSC_COLOR.into()),
+            versin: materials.add(VERSIN_COLOR.into()),
+            coversin: materials.add(COVERSIN_COLOR.into()),
+            exsec: materials.add(EXSEC_COLOR.into()),
+            excsc: materials.add(EXCSC_COLOR.into()),
+            haversin: materials.add(HAVERSIN_COLOR.into()),
+        }
+    }
+}
+
+impl LineMaterials {
+    pub fn grid(&self, shape: GridShape) -> Handle<ColorMaterial> {
+        match shape {
+            GridShape::MinorLines => self.grid_minor.clone(),
+            GridShape::MajorLines => self.grid_major.clone(),
+            GridShape::Ticks => self.axis.clone(),
+        }
+    }
+
+    pub fn function(&self, function: Function) -> Handle<ColorMaterial> {
+        match function {
+            Function::Cos => self.cos.clone(),
+            Function::Sin => self.sin.clone(),
+            Function::Tan => self.tan.clone(),
+            Function::Cot => self.cot.clone(),
+            Function::Sec => self.sec.clone(),
+            Function::Csc => self.csc.clone(),
+            Function::Versin => self.versin.clone(),
+            Function::Coversin => self.coversin.clone(),
+            Function::Exsec => self.exsec.clone(),
+            Function::Excsc => self.excsc.clone(),
+            Function::Haversin => self.haversin.clone(),
+        }
+    }
+}
+
+/// The path drawn for `function`'s line, see [`function_segments`].
+fn function_path(geometry: &TrigGeometry, function: Function, viewport: &Bounds) -> Path {
+    segments_path(&function_segments(geometry, function, viewport))
+}
+
+fn circle_shape(material: Handle<ColorMaterial>, radius: f32) -> impl Bundle {
+    let circle = shapes::Circle {
+        radius,
+        ..shapes::Circle::default()
+    };
+
+    GeometryBuilder::build_as(
+        &circle,
+        material,
+        TessellationMode::Stroke(StrokeOptions::default().with_line_width(2.)),
+        Transform::default(),
+    )
+}
+
+pub fn setup(
+    commands: &mut Commands,
+    line_materials: Res<LineMaterials>,
+    radius: Res<Radius>,
+    theme: Res<Theme>,
+    asset_server: Res<AssetServer>,
+) {
+    let font: Handle<Font> = asset_server.load("fonts/FiraMono-Regular.ttf");
+
+    commands
+        // the grid is filled in by `grid_system`
+        .spawn(stroke_behind(
+            &segments_path(&[]),
+            line_materials.grid_minor.clone(),
+            1.,
+        ))
+        .with(GridShape::MinorLines)
+        .spawn(stroke_behind(
+            &segments_path(&[]),
+            line_materials.grid_major.clone(),
+            1.,
+        ))
+        .with(GridShape::MajorLines)
+        .spawn(stroke_behind(
+            &segments_path(&[]),
+            line_materials.axis.clone(),
+            1.,
+        ))
+        .with(GridShape::Ticks)
+        // the axes are stretched across the viewport by `axes_system`
+        .spawn_line(
+            line_materials.axis.clone(),
+            (Vec2::zero(), Vec2::zero()),
+            1.,
+        )
+        .with(XAxis)
+        .spawn_line(
+            line_materials.axis.clone(),
+            (Vec2::zero(), Vec2::zero()),
+            1.,
+        )
+        .with(YAxis)
+        // the circle and lines are spawned once and then rebuilt in place
+        // by their systems whenever theta or the radius changes
+        .spawn(circle_shape(line_materials.circle.clone(), radius.0))
+        .with(Circle)
+        .with(CircleOnly)
+        .spawn(fill(&polygon_path(&[]), line_materials.arc.clone()))
+        .with(AngleArc)
+        .with(CircleOnly)
+        .spawn_line(
+            line_materials.radius.clone(),
+            (Vec2::zero(), Vec2::zero()),
+            1.,
+        )
+        .with(RadiusLine)
+        .with(CircleOnly)
+        .spawn_line(line_materials.sec.clone(), (Vec2::zero(), Vec2::zero()), 2.)
+        .with(FunctionLine(Function::Sec))
+        .spawn_line(line_materials.csc.clone(), (Vec2::zero(), Vec2::zero()), 2.)
+        .with(FunctionLine(Function::Csc))
+        .spawn_line(line_materials.cos.clone(), (Vec2::zero(), Vec2::zero()), 2.)
+        .with(CosLine)
+        .with(FunctionLine(Function::Cos))
+        .spawn_line(line_materials.sin.clone(), (Vec2::zero(), Vec2::zero()), 2.)
+        .with(SinLine)
+        .with(FunctionLine(Function::Sin))
+        .spawn_line(line_materials.tan.clone(), (Vec2::zero(), Vec2::zero()), 2.)
+        .with(FunctionLine(Function::Tan))
+        .spawn_line(line_materials.cot.clone(), (Vec2::zero(), Vec2::zero()), 2.)
+        .with(FunctionLine(Function::Cot));
+
+    // the wave graph is laid out by `resize_circle_system` and drawn by the
+    // wave systems once it knows the size of the window
+    commands
+        .spawn_line(
+            line_materials.axis.clone(),
+            (Vec2::zero(), Vec2::zero()),
+            1.,
+        )
+        .with(WaveAxis)
+        .spawn(stroke(
+            &segments_path(&[]),
+            line_materials.radius.clone(),
+            1.,
+        ))
+        .with(WaveConnector)
+        .with(CircleOnly);
+    for function in WAVE_FUNCTIONS.iter() {
+        commands
+            .spawn(stroke(
+                &polylines_path(&[]),
+                line_materials.function(*function),
+                2.,
+            ))
+            .with(WaveCurve(*function))
+            .spawn(fill(
+                &shapes::Circle {
+                    radius: 4.,
+                    ..shapes::Circle::default()
+                },
+                line_materials.function(*function),
+            ))
+            .with(WaveMarker(*function))
+            .spawn(stroke(
+                &segments_path(&[]),
+                line_materials.function(*function),
+                1.,
+            ))
+            .with(WaveAsymptotes(*function));
+    }
+
+    commands
+        .spawn(stroke(
+            &shapes::Circle {
+                radius: 8.,
+                ..shapes::Circle::default()
+            },
+            line_materials.highlight.clone(),
+            2.,
+        ))
+        .with(SnapCue);
+
+    // the inverse functions' shapes are drawn by `inverse_system`
+    commands
+        .spawn(fill(&polygon_path(&[]), line_materials.arc.clone()))
+        .with(InverseRange)
+        .spawn(stroke(
+            &segments_path(&[]),
+            line_materials.highlight.clone(),
+            3.,
+        ))
+        .with(InverseGuide)
+        .spawn(stroke(
+            &segments_path(&[]),
+            line_materials.highlight.clone(),
+            2.,
+        ))
+        .with(InverseAngles)
+        .spawn(fill(
+            &shapes::Circle {
+                radius: 6.,
+                ..shapes::Circle::default()
+            },
+            line_materials.highlight.clone(),
+        ))
+        .with(InverseHandle);
+
+    // the hyperbola is drawn by `hyperbola_system` while it's shown
+    for shape in [
+        HyperbolaShape::Area,
+        HyperbolaShape::Asymptotes,
+        HyperbolaShape::Curve,
+        HyperbolaShape::RadiusLine,
+    ]
+    .iter()
+    {
+        commands
+            .spawn(stroke(
+                &segments_path(&[]),
+                line_materials.circle.clone(),
+                1.,
+            ))
+            .with(*shape)
+            .with(HyperbolaOnly);
+    }
+    for function in HyperbolicFunction::ALL.iter() {
+        commands
+            .spawn(stroke(
+                &segments_path(&[]),
+                line_materials.function(function.circular()),
+                2.,
+            ))
+            .with(HyperbolicLine(*function))
+            .with(HyperbolaOnly);
+    }
+
+    // the complex plane's shapes are drawn by `complex_system`
+    commands
+        .spawn(stroke(
+            &segments_path(&[]),
+            line_materials.highlight.clone(),
+            2.,
+        ))
+        .with(ComplexLines)
+        .spawn(stroke(
+            &polygon_path(&[]),
+            line_materials.highlight.clone(),
+            2.,
+        ))
+        .with(RootsPolygon);
+    for point in [ComplexPoint::Factor, ComplexPoint::Product].iter() {
+        commands
+            .spawn(fill(
+                &shapes::Circle {
+                    radius: 6.,
+                    ..shapes::Circle::default()
+                },
+                line_materials.highlight.clone(),
+            ))
+            .with(*point);
+    }
+
+    // the canvas labels are placed by `canvas_labels_system`
+    let label_style = |color| TextStyle {
+        font_size: LABEL_FONT_SIZE,
+        color,
+        alignment: TextAlignment {
+            vertical: VerticalAlign::Center,
+            horizontal: HorizontalAlign::Center,
+        },
+    };
+    commands
+        .spawn(Text2dBundle {
+            text: Text {
+                value: "θ".into(),
+                font: font.clone(),
+                style: label_style(color(theme.palette().highlight)),
+            },
+            ..Default::default()
+        })
+        .with(ThetaLabel)
+        .with(CircleOnly);
+    for (name, _) in axis_labels(radius.0, LABEL_FONT_SIZE).iter() {
+        commands
+            .spawn(Text2dBundle {
+                text: Text {
+                    value: name.to_string(),
+                    font: font.clone(),
+                    style: label_style(color(theme.palette().grid_label)),
+                },
+                visible: Visible {
+                    is_visible: false,
+                    is_transparent: true,
+                },
+                ..Default::default()
+            })
+            .with(AxisName);
+    }
+    for function in Function::ALL.iter() {
+        commands
+            .spawn(Text2dBundle {
+                text: Text {
+                    value: format!("{} θ", function.name()),
+                    font: font.clone(),
+                    style: label_style(function_color(*function)),
+                },
+                ..Default::default()
+            })
+            .with(FunctionLabel(*function));
+    }
+
+    // the archaic overlay starts out hidden, see `ShownFunctions::default`
+    for function in Function::ARCHAIC.iter() {
+        commands
+            .spawn_line(
+                line_materials.function(*function),
+                (Vec2::zero(), Vec2::zero()),
+                line_width(*function),
+            )
+            .with(ArchaicLine)
+            .with(FunctionLine(*function));
+    }
+}
+
+/// The help text and the readout panel, only spawned when the plugin's UI is on.
+pub fn ui_setup(
+    commands: &mut Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    asset_server: Res<AssetServer>,
+) {
+    let font: Handle<Font> = asset_server.load("fonts/FiraMono-Regular.ttf");
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                border: Rect::all(Val::Px(2.)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    bottom: Val::Px(10.),
+                    left: Val::Px(10.),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            material: materials.add(Color::rgba_linear(0.5, 0.5, 0.5, 0.5).into()),
+            visible: Visible {
+                is_transparent: true,
+                is_visible: true,
+            },
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        border: Rect::all(Val::Px(2.)),
+                        padding: Rect::all(Val::Px(10.)),
+                        ..Default::default()
+                    },
+                    material: materials.add(Color::rgba_linear(0.02, 0.02, 0.02, 0.75).into()),
+                    visible: Visible {
+                        is_transparent: true,
+                        is_visible: true,
+                    },
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        style: Style {
+                            position_type: PositionType::Relative,
+                            ..Default::default()
+                        },
+                        text: Text {
+                            value: HELP_TEXT.into(),
+                            font: asset_server.load("fonts/FiraSans-Regular.ttf"),
+                            style: TextStyle {
+                                font_size: 18.0,
+                                color: Color::rgb_linear(0.7, 0.7, 0.7),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    });
+                });
+        })
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(10.),
+                    top: Val::Px(10.),
+                    ..Default::default()
+                },
+                padding: Rect::all(Val::Px(10.)),
+                align_items: AlignItems::FlexStart,
+                flex_direction: FlexDirection::ColumnReverse,
+                ..Default::default()
+            },
+            visible: Visible {
+                is_visible: true,
+                is_transparent: true,
+            },
+            material: materials.add(Color::rgba_linear(0.1, 0.1, 0.1, 0.5).into()),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn(TextBundle {
+                    text: Text {
+                        value: "θ = ".to_string(),
+                        font: font.clone(),
+                        style: TextStyle {
+                            font_size: 18.0,
+                            color: Color::rgb_linear(0.7, 0.7, 0.7),
+                            ..Default::default()
+                        },
+                    },
+                    ..Default::default()
+                })
+                .with(ThetaText)
+                // filled in and shown by `inverse_text_system`
+                .spawn(TextBundle {
+                    style: Style {
+                        display: Display::None,
+                        ..Default::default()
+                    },
+                    text: Text {
+                        value: String::new(),
+                        font: font.clone(),
+                        style: TextStyle {
+                            font_size: 18.0,
+                            color: Color::rgb_linear(0.9, 0.9, 0.9),
+                            ..Default::default()
+                        },
+                    },
+                    ..Default::default()
+                })
+                .with(InverseText)
+                // filled in and shown by `complex_text_system`
+                .spawn(TextBundle {
+                    style: Style {
+                        display: Display::None,
+                        ..Default::default()
+                    },
+                    text: Text {
+                        value: String::new(),
+                        font: font.clone(),
+                        style: TextStyle {
+                            font_size: 18.0,
+                            color: Color::rgb_linear(0.9, 0.9, 0.9),
+                            ..Default::default()
+                        },
+                    },
+                    ..Default::default()
+                })
+                .with(ComplexText)
+                // filled in and shown by `hyperbolic_text_system`
+                .spawn(TextBundle {
+                    style: Style {
+                        display: Display::None,
+                        ..Default::default()
+                    },
+                    text: Text {
+                        value: String::new(),
+                        font: font.clone(),
+                        style: TextStyle {
+                            font_size: 18.0,
+                            color: Color::rgb_linear(0.9, 0.9, 0.9),
+                            ..Default::default()
+                        },
+                    },
+                    ..Default::default()
+                })
+                .with(HyperbolicText)
+                .spawn(TextBundle {
+                    text: Text {
+                        value: "cos θ = ".to_string(),
+                        font: font.clone(),
+                        style: TextStyle {
+                            font_size: 18.0,
+                            color: COS_COLOR,
+                            ..Default::default()
+                        },
+                    },
+                    ..Default::default()
+                })
+                .with(CosText)
+                .with(FunctionText(Function::Cos))
+                .spawn(TextBundle {
+                    text: Text {
+                        value: "sin θ = ".to_string(),
+                        font: font.clone(),
+                        style: TextStyle {
+                            font_size: 18.0,
+                            color: SIN_COLOR,
+                            ..Default::default()
+                        },
+                    },
+                    ..Default::default()
+                })
+                .with(SinText)
+                .with(FunctionText(Function::Sin))
+                .spawn(TextBundle {
+                    text: Text {
+                        value: "tan θ = ".to_string(),
+                        font: font.clone(),
+                        style: TextStyle {
+                            font_size: 18.0,
+                            color: TAN_COLOR,
+                            ..Default::default()
+                        },
+                    },
+                    ..Default::default()
+                })
+                .with(TanText)
+                .with(FunctionText(Function::Tan))
+                .spawn(TextBundle {
+                    text: Text {
+                        value: "cot θ = ".to_string(),
+                        font: font.clone(),
+                        style: TextStyle {
+                            font_size: 18.0,
+                            color: COT_COLOR,
+                            ..Default::default()
+                        },
+                    },
+                    ..Default::default()
+                })
+                .with(CotText)
+                .with(FunctionText(Function::Cot))
+                .spawn(TextBundle {
+                    text: Text {
+                        value: "sec θ = ".to_string(),
+                        font: font.clone(),
+                        style: TextStyle {
+                            font_size: 18.0,
+                            color: SEC_COLOR,
+                            ..Default::default()
+                        },
+                    },
+                    ..Default::default()
+                })
+                .with(SecText)
+                .with(FunctionText(Function::Sec))
+                .spawn(TextBundle {
+                    text: Text {
+                        value: "csc θ = ".to_string(),
+                        font: font.clone(),
+                        style: TextStyle {
+                            font_size: 18.0,
+                            color: CSC_COLOR,
+                            ..Default::default()
+                        },
+                    },
+                    ..Default::default()
+                })
+                .with(CscText)
+                .with(FunctionText(Function::Csc));
+
+            for function in Function::ARCHAIC.iter() {
+                parent
+                    .spawn(TextBundle {
+                        text: Text {
+                            value: format!("{} θ = ", function.name()),
+                            font: font.clone(),
+                            style: TextStyle {
+                                font_size: 18.0,
+                                color: function_color(*function),
+                                ..Default::default()
+                            },
+                        },
+                        ..Default::default()
+                    })
+                    .with(ArchaicText)
+                    .with(FunctionText(*function));
+            }
+        });
+}
+
+pub fn animate_system(
+    theta: Res<Theta>,
+    paused: Res<Paused>,
+    mut animation: ResMut<AnimationSettings>,
+    time: Res<Time>,
+    mut set_theta: ResMut<Events<SetTheta>>,
+) {
+    if paused.0 {
+        return;
+    };
+
+    set_theta.send(SetTheta {
+        theta: animation.advance(theta.0, time.delta_seconds()),
+        source: ThetaSource::Animation,
+    });
+}
+
+/// Moves θ to where this frame's requests ask for. Anything else wins over
+/// the animation, which only ever nudges θ along, and otherwise the last
+/// request wins. That makes the order of the input stage the priority: the
+/// mouse comes after the keyboard, so dragging beats the arrow keys.
+pub fn apply_theta_system(
+    mut reader: Local<EventReader<SetTheta>>,
+    requests: Res<Events<SetTheta>>,
+    mut theta: ResMut<Theta>,
+    mut changes: ResMut<Events<ThetaChanged>>,
+) {
+    let mut winner: Option<&SetTheta> = None;
+    for request in reader.iter(&requests) {
+        let overrides = match winner {
+            Some(winner) => {
+                request.source != ThetaSource::Animation || winner.source == ThetaSource::Animation
+            }
+            None => true,
+        };
+        if overrides {
+            winner = Some(request);
+        }
+    }
+
+    if let Some(request) = winner {
+        // only write on change, everything downstream redraws when θ is mutated
+        if request.theta != theta.0 {
+            changes.send(ThetaChanged {
+                old: theta.0,
+                new: request.theta,
+                source: request.source,
+            });
+            theta.0 = request.theta;
+        }
+    }
+}
+
+/// Recomputes the geometry, but only touches the resource when something
+/// actually moved so the drawing systems below can skip unchanged frames.
+pub fn geometry_system(theta: Res<Theta>, radius: Res<Radius>, mut geometry: ResMut<TrigGeometry>) {
+    let new_geometry = TrigGeometry::new(theta.0, radius.0);
+    if *geometry != new_geometry {
+        *geometry = new_geometry;
+    }
+}
+
+pub fn theta_text_system(
+    geometry: Res<TrigGeometry>,
+    unit: Res<AngleUnit>,
+    mut text: Query<&mut Text, With<ThetaText>>,
+) {
+    let value = format!("θ = {}", unit.format(geometry.theta));
+    for mut txt in text.iter_mut() {
+        // only assign on change so the text isn't laid out again every frame
+        if txt.value != value {
+            txt.value = value.clone();
+        }
+    }
+}
+
+/// Stretches the axes across whatever part of the scene is visible. The x axis
+/// doubles as the θ axis of the wave graph.
+pub fn axes_system(
+    commands: &mut Commands,
+    viewport: ChangedRes<Viewport>,
+    materials: Res<LineMaterials>,
+    x_axis: Query<Entity, With<XAxis>>,
+    y_axis: Query<Entity, With<YAxis>>,
+) {
+    let Bounds { min, max } = viewport.0;
+    for entity in x_axis.iter() {
+        commands.update_line(
+            entity,
+            materials.axis.clone(),
+            (Vec2::new(min.x, 0.), Vec2::new(max.x, 0.)),
+            1.,
+        );
+    }
+    for entity in y_axis.iter() {
+        commands.update_line(
+            entity,
+            materials.axis.clone(),
+            (Vec2::new(0., min.y), Vec2::new(0., max.y)),
+            1.,
+        );
+    }
+}
+
+/// Recolours the canvas when the theme changes: the background, the lines drawn
+/// in the theme's colours and the labels on them. The lines share their
+/// materials, so it's enough to change those. The UI panels, buttons and help
+/// overlay aren't themed, they stay dark over either canvas. The colours the
+/// app started with are left alone, the host may have picked its own
+/// background.
+pub fn theme_system(
+    mut previous: Local<Option<Theme>>,
+    theme: Res<Theme>,
+    line_materials: Res<LineMaterials>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut clear_color: ResMut<ClearColor>,
+    mut labels: QuerySet<(
+        Query<&mut Text, With<ThetaLabel>>,
+        Query<&mut Text, With<AxisName>>,
+    )>,
+) {
+    if previous
+        .replace(*theme)
+        .map_or(true, |previous| previous == *theme)
+    {
+        return;
+    }
+
+    let palette = theme.palette();
+    let recolors = [
+        (&line_materials.axis, palette.axis),
+        (&line_materials.grid_minor, palette.grid_minor),
+        (&line_materials.grid_major, palette.grid_major),
+        (&line_materials.arc, palette.arc),
+        (&line_materials.highlight, palette.highlight),
+        (&line_materials.circle, palette.circle),
+        (&line_materials.radius, palette.radius),
+    ];
+    for (handle, rgb) in recolors.iter() {
+        if let Some(material) = materials.get_mut(*handle) {
+            material.color = color(*rgb);
+        }
+    }
+    clear_color.0 = color(palette.background);
+    for mut text in labels.q0_mut().iter_mut() {
+        text.style.color = color(palette.highlight);
+    }
+    for mut text in labels.q1_mut().iter_mut() {
+        text.style.color = color(palette.grid_label);
+    }
+}
+
+/// Redraws the grid, its ticks and its labels when the circle, the viewport,
+/// the angle unit or the theme changes, and clears them when the grid is
+/// turned off.
+#[allow(clippy::too_many_arguments)]
+pub fn grid_system(
+    commands: &mut Commands,
+    mut drawn: Local<Option<(f32, Viewport, AngleUnit, bool, Theme)>>,
+    radius: Res<Radius>,
+    viewport: Res<Viewport>,
+    unit: Res<AngleUnit>,
+    grid_visible: Res<GridVisible>,
+    theme: Res<Theme>,
+    materials: Res<LineMaterials>,
+    asset_server: Res<AssetServer>,
+    shapes: Query<(Entity, &GridShape)>,
+    labels: Query<Entity, With<GridLabel>>,
+) {
+    let state = (radius.0, *viewport, *unit, grid_visible.0, *theme);
+    if *drawn == Some(state) {
+        return;
+    }
+    *drawn = Some(state);
+
+    let grid = if grid_visible.0 {
+        Grid::new(radius.0, &viewport.0, TICK_SIZE, *unit)
+    } else {
+        Grid::default()
+    };
+
+    for (entity, shape) in shapes.iter() {
+        let segments = match shape {
+            GridShape::MinorLines => &grid.minor_lines,
+            GridShape::MajorLines => &grid.major_lines,
+            GridShape::Ticks => &grid.ticks,
+        };
+        commands.insert(
+            entity,
+            stroke_behind(&segments_path(segments), materials.grid(*shape), 1.),
+        );
+    }
+
+    // there are as many labels as fit on screen, so they're simply respawned
+    for entity in labels.iter() {
+        commands.despawn(entity);
+    }
+    let font: Handle<Font> = asset_server.load("fonts/FiraMono-Regular.ttf");
+    for label in grid.labels {
+        commands
+            .spawn(Text2dBundle {
+                text: Text {
+                    value: label.text,
+                    font: font.clone(),
+                    style: TextStyle {
+                        font_size: 14.0,
+                        color: color(theme.palette().grid_label),
+                        alignment: TextAlignment {
+                            vertical: VerticalAlign::Center,
+                            horizontal: HorizontalAlign::Center,
+                        },
+                    },
+                },
+                transform: Transform::from_translation(to_vec2(label.position).extend(-0.5)),
+                ..Default::default()
+            })
+            .with(GridLabel);
+    }
+}
+
+pub fn angle_arc_system(
+    commands: &mut Commands,
+    geometry: ChangedRes<TrigGeometry>,
+    materials: Res<LineMaterials>,
+    arc: Query<Entity, With<AngleArc>>,
+) {
+    let points = angle_arc(geometry.theta, arc_radius(geometry.radius));
+    for entity in arc.iter() {
+        commands.insert(entity, fill(&polygon_path(&points), materials.arc.clone()));
+    }
+}
+
+/// Puts "θ" on the angle arc and the name of each shown function next to its
+/// line, keeping the names clear of each other. Only the transforms change,
+/// so this runs every frame.
+pub fn canvas_labels_system(
+    geometry: Res<TrigGeometry>,
+    viewport: Res<Viewport>,
+    shown: Res<ShownFunctions>,
+    hyperbolic: Res<HyperbolicView>,
+    mut labels: QuerySet<(
+        Query<&mut Transform, With<ThetaLabel>>,
+        Query<(&FunctionLabel, &mut Transform, &mut Visible)>,
+    )>,
+) {
+    // the functions' lines aren't drawn on the hyperbola, so neither are their names
+    let shown = if hyperbolic.0 {
+        Vec::new()
+    } else {
+        shown.ordered()
+    };
+    let placed = CanvasLabels::new(&geometry, &viewport.0, &shown);
+    for mut transform in labels.q0_mut().iter_mut() {
+        let translation = to_vec2(placed.theta).extend(2.);
+        if transform.translation != translation {
+            transform.translation = translation;
+        }
+    }
+
+    for (label, mut transform, mut visible) in labels.q1_mut().iter_mut() {
+        let centre = placed
+            .functions
+            .iter()
+            .find(|(function, _)| *function == label.0)
+            .map(|(_, centre)| *centre);
+        let is_visible = centre.is_some();
+        if visible.is_visible != is_visible {
+            visible.is_visible = is_visible;
+        }
+        if let Some(centre) = centre {
+            let translation = to_vec2(centre).extend(2.);
+            if transform.translation != translation {
+                transform.translation = translation;
+            }
+        }
+    }
+}
+
+pub fn circle_system(
+    commands: &mut Commands,
+    radius: ChangedRes<Radius>,
+    materials: Res<LineMaterials>,
+    query: Query<Entity, With<Circle>>,
+) {
+    for entity in query.iter() {
+        commands.insert(entity, circle_shape(materials.circle.clone(), radius.0));
+    }
+}
+
+pub fn radius_line_system(
+    commands: &mut Commands,
+    query: Query<Entity, With<RadiusLine>>,
+    geometry: ChangedRes<TrigGeometry>,
+    materials: Res<LineMaterials>,
+) {
+    for entity in query.iter() {
+        commands.update_line(
+            entity,
+            materials.radius.clone(),
+            to_line(geometry.radius_line),
+            1.,
+        );
+    }
+}
+
+pub fn cos_line_system(
+    commands: &mut Commands,
+    query: Query<Entity, With<CosLine>>,
+    geometry: ChangedRes<TrigGeometry>,
+    shown: Res<ShownFunctions>,
+    hyperbolic: Res<HyperbolicView>,
+    materials: Res<LineMaterials>,
+) {
+    if !shown.contains(Function::Cos) || hyperbolic.0 {
+        return;
+    }
+
+    for entity in query.iter() {
+        commands.update_line(
+            entity,
+            materials.cos.clone(),
+            to_line(geometry.cos_line),
+            2.,
+        );
+    }
+}
+
+pub fn sin_line_system(
+    commands: &mut Commands,
+    query: Query<Entity, With<SinLine>>,
+    geometry: ChangedRes<TrigGeometry>,
+    shown: Res<ShownFunctions>,
+    hyperbolic: Res<HyperbolicView>,
+    materials: Res<LineMaterials>,
+) {
+    if !shown.contains(Function::Sin) || hyperbolic.0 {
+        return;
+    }
+
+    for entity in query.iter() {
+        commands.update_line(
+            entity,
+            materials.sin.clone(),
+            to_line(geometry.sin_line),
+            2.,
+        );
+    }
+}
+
+/// Draws tan, cot, sec, csc, exsec and excsc, which can run off to infinity
+/// and so also have to be redrawn when the viewport changes.
+#[allow(clippy::too_many_arguments)]
+pub fn unbounded_lines_system(
+    commands: &mut Commands,
+    mut drawn: Local<Option<(TrigGeometry, Viewport)>>,
+    geometry: Res<TrigGeometry>,
+    viewport: Res<Viewport>,
+    shown: Res<ShownFunctions>,
+    hyperbolic: Res<HyperbolicView>,
+    materials: Res<LineMaterials>,
+    lines: Query<(Entity, &FunctionLine)>,
+) {
+    // `function_visibility_system` redraws them when the circle is back
+    if hyperbolic.0 || *drawn == Some((*geometry, *viewport)) {
+        return;
+    }
+    *drawn = Some((*geometry, *viewport));
+
+    for (entity, line) in lines.iter() {
+        if line.0.is_unbounded() && shown.contains(line.0) {
+            let path = function_path(&geometry, line.0, &viewport.0);
+            commands.insert(
+                entity,
+                stroke(&path, materials.function(line.0), line_width(line.0)),
+            );
+        }
+    }
+}
+
+pub fn archaic_lines_system(
+    commands: &mut Commands,
+    lines: Query<(Entity, &FunctionLine), With<ArchaicLine>>,
+    geometry: ChangedRes<TrigGeometry>,
+    shown: Res<ShownFunctions>,
+    hyperbolic: Res<HyperbolicView>,
+    materials: Res<LineMaterials>,
+) {
+    if hyperbolic.0 {
+        return;
+    }
+
+    for (entity, line) in lines.iter() {
+        // exsec and excsc are left to `unbounded_lines_system`
+        if shown.contains(line.0) && !line.0.is_unbounded() {
+            commands.update_line(
+                entity,
+                materials.function(line.0),
+                to_line(geometry.segment(line.0)),
+                line_width(line.0),
+            );
+        }
+    }
+}
+
+/// Keeps the inverse function's value in step with θ when something else
+/// moves it, e.g. the arrow keys or the animation.
+pub fn inverse_follow_system(
+    mut reader: Local<EventReader<ThetaChanged>>,
+    changes: Res<Events<ThetaChanged>>,
+    mut inverse: ResMut<Inverse>,
+) {
+    let change = match reader.latest(&changes) {
+        Some(change) => *change,
+        None => return,
+    };
+    let value = match inverse
+        .function
+        .and_then(|function| function.value_of(change.new))
+    {
+        Some(value) => value,
+        None => return,
+    };
+    // the value picked on the guide moves θ to its principal value, which
+    // mustn't nudge the value in turn
+    let same_angle = inverse
+        .function
+        .and_then(|function| function.principal(inverse.value))
+        == Some(change.new);
+    if !same_angle && inverse.value != value {
+        inverse.value = value;
+    }
+}
+
+/// Shades the principal range of the inverse function, draws the line its
+/// value is picked on and the radius lines to every angle with that value:
+/// solid to the principal value, dashed to the others.
+///
+/// Everything is hidden while no inverse function is picked. Like the lines
+/// of hidden functions, they're only rebuilt while shown.
+pub fn inverse_system(
+    commands: &mut Commands,
+    mut drawn: Local<Option<(Option<InverseFunction>, f32, f32, Viewport)>>,
+    inverse: Res<Inverse>,
+    radius: Res<Radius>,
+    viewport: Res<Viewport>,
+    materials: Res<LineMaterials>,
+    mut shapes: QuerySet<(
+        Query<(Entity, &mut Visible), With<InverseRange>>,
+        Query<(Entity, &mut Visible), With<InverseGuide>>,
+        Query<(Entity, &mut Visible), With<InverseAngles>>,
+        Query<(&mut Transform, &mut Visible), With<InverseHandle>>,
+    )>,
+) {
+    let key = (inverse.function, inverse.value, radius.0, *viewport);
+    if *drawn == Some(key) {
+        return;
+    }
+    *drawn = Some(key);
+
+    let function = match inverse.function {
+        Some(function) => function,
+        None => {
+            for (_, mut visible) in shapes.q0_mut().iter_mut() {
+                visible.is_visible = false;
+            }
+            for (_, mut visible) in shapes.q1_mut().iter_mut() {
+                visible.is_visible = false;
+            }
+            for (_, mut visible) in shapes.q2_mut().iter_mut() {
+                visible.is_visible = false;
+            }
+            for (_, mut visible) in shapes.q3_mut().iter_mut() {
+                visible.is_visible = false;
+            }
+            return;
+        }
+    };
+
+    let (start, end) = function.principal_range();
+    let range = sector(start, end, radius.0);
+    for (entity, _) in shapes.q0_mut().iter_mut() {
+        commands.insert(entity, fill(&polygon_path(&range), materials.arc.clone()));
+    }
+
+    let guide: Vec<_> = function
+        .guide(radius.0, &viewport.0)
+        .clip(&viewport.0)
+        .into_iter()
+        .collect();
+    let material = materials.function(function.function());
+    for (entity, _) in shapes.q1_mut().iter_mut() {
+        commands.insert(entity, stroke(&segments_path(&guide), material.clone(), 3.));
+    }
+
+    let lines = function.angle_lines(inverse.value, radius.0);
+    for (entity, _) in shapes.q2_mut().iter_mut() {
+        commands.insert(
+            entity,
+            stroke(&segments_path(&lines), materials.highlight.clone(), 2.),
+        );
+    }
+
+    let translation = to_vec2(function.handle(inverse.value, radius.0)).extend(2.);
+    for (mut transform, mut visible) in shapes.q3_mut().iter_mut() {
+        visible.is_visible = true;
+        transform.translation = translation;
+    }
+}
+
+/// Shows the inverse function's angles in the readout, and the value being
+/// typed in.
+pub fn inverse_text_system(
+    inverse: Res<Inverse>,
+    unit: Res<AngleUnit>,
+    mut texts: Query<(&mut Text, &mut Style), With<InverseText>>,
+) {
+    let (display, value) = match inverse.function {
+        Some(function) if !inverse.entry.is_empty() => (
+            Display::Flex,
+            format!("{} {}_", function.name(), inverse.entry.as_str()),
+        ),
+        Some(function) => (Display::Flex, function.readout(inverse.value, *unit)),
+        None => (Display::None, String::new()),
+    };
+    for (mut text, mut style) in texts.iter_mut() {
+        if style.display != display {
+            style.display = display;
+        }
+        if text.value != value {
+            text.value = value.clone();
+        }
+    }
+}
+
+/// Names the axes Re and Im and draws the factor, the product and the roots
+/// of unity while the circle is shown as the complex plane. The lines are only
+/// rebuilt while shown, see `function_visibility_system`.
+pub fn complex_system(
+    commands: &mut Commands,
+    mut drawn: Local<Option<(ComplexPlane, TrigGeometry)>>,
+    complex: Res<ComplexPlane>,
+    geometry: Res<TrigGeometry>,
+    materials: Res<LineMaterials>,
+    mut shapes: QuerySet<(
+        Query<(Entity, &mut Visible), With<ComplexLines>>,
+        Query<(Entity, &mut Visible), With<RootsPolygon>>,
+        Query<(&ComplexPoint, &mut Transform, &mut Visible)>,
+        Query<(&Text, &mut Transform, &mut Visible), With<AxisName>>,
+    )>,
+) {
+    if *drawn == Some((*complex, *geometry)) {
+        return;
+    }
+    *drawn = Some((*complex, *geometry));
+
+    let radius = geometry.radius;
+    let factor = complex.factor.filter(|_| complex.enabled);
+    let product = complex.product(geometry.theta).filter(|_| complex.enabled);
+    let roots = complex.roots.filter(|_| complex.enabled);
+
+    for (entity, mut visible) in shapes.q0_mut().iter_mut() {
+        if let (Some(factor), Some(product)) = (factor, product) {
+            let mut lines = Segment::new(Point::ORIGIN, factor.to_point(radius)).dashes(6., 4.);
+            lines.push(Segment::new(Point::ORIGIN, product.to_point(radius)));
+            commands.insert(
+                entity,
+                stroke(&segments_path(&lines), materials.highlight.clone(), 2.),
+            );
+        } else if visible.is_visible {
+            visible.is_visible = false;
+        }
+    }
+
+    for (entity, mut visible) in shapes.q1_mut().iter_mut() {
+        if let Some(n) = roots {
+            let corners: Vec<_> = roots_of_unity(n)
+                .iter()
+                .map(|root| root.to_point(radius))
+                .collect();
+            commands.insert(
+                entity,
+                stroke(&polygon_path(&corners), materials.highlight.clone(), 2.),
+            );
+        } else if visible.is_visible {
+            visible.is_visible = false;
+        }
+    }
+
+    for (point, mut transform, mut visible) in shapes.q2_mut().iter_mut() {
+        let number = match point {
+            ComplexPoint::Factor => factor,
+            ComplexPoint::Product => product,
+        };
+        if visible.is_visible != number.is_some() {
+            visible.is_visible = number.is_some();
+        }
+        if let Some(number) = number {
+            let translation = to_vec2(number.to_point(radius)).extend(2.);
+            if transform.translation != translation {
+                transform.translation = translation;
+            }
+        }
+    }
+
+    let names = axis_labels(radius, LABEL_FONT_SIZE);
+    for (text, mut transform, mut visible) in shapes.q3_mut().iter_mut() {
+        if visible.is_visible != complex.enabled {
+            visible.is_visible = complex.enabled;
+        }
+        if let Some((_, position)) = names.iter().find(|(name, _)| *name == text.value) {
+            let translation = to_vec2(*position).extend(2.);
+            if transform.translation != translation {
+                transform.translation = translation;
+            }
+        }
+    }
+}
+
+/// Shows Euler's formula at θ in the readout on the complex plane, and what
+/// multiplying by the factor does.
+pub fn complex_text_system(
+    complex: Res<ComplexPlane>,
+    geometry: Res<TrigGeometry>,
+    unit: Res<AngleUnit>,
+    mut texts: Query<(&mut Text, &mut Style), With<ComplexText>>,
+) {
+    let (display, value) = if complex.enabled {
+        (Display::Flex, complex.readout(geometry.theta, *unit))
+    } else {
+        (Display::None, String::new())
+    };
+    for (mut text, mut style) in texts.iter_mut() {
+        if style.display != display {
+            style.display = display;
+        }
+        if text.value != value {
+            text.value = value.clone();
+        }
+    }
+}
+
+/// Draws the unit hyperbola, the area that makes up the hyperbolic angle and
+/// the lines of the hyperbolic functions, with θ as the hyperbolic angle, see
+/// [`crate::hyperbolic`]. Nothing is rebuilt while the circle is shown, as
+/// `view_system` keeps it all hidden then.
+#[allow(clippy::too_many_arguments)]
+pub fn hyperbola_system(
+    commands: &mut Commands,
+    mut drawn: Local<Option<(HyperbolicGeometry, Viewport)>>,
+    view: Res<HyperbolicView>,
+    geometry: Res<TrigGeometry>,
+    viewport: Res<Viewport>,
+    materials: Res<LineMaterials>,
+    shapes: Query<(Entity, &HyperbolaShape)>,
+    lines: Query<(Entity, &HyperbolicLine)>,
+) {
+    if !view.0 {
+        *drawn = None;
+        return;
+    }
+    let hyperbolic = HyperbolicGeometry::new(parameter(geometry.theta), geometry.radius);
+    if *drawn == Some((hyperbolic, *viewport)) {
+        return;
+    }
+    *drawn = Some((hyperbolic, *viewport));
+
+    for (entity, shape) in shapes.iter() {
+        let (path, material, width) = match shape {
+            HyperbolaShape::Area => {
+                commands.insert(
+                    entity,
+                    fill(&polygon_path(&hyperbolic.area()), materials.arc.clone()),
+                );
+                continue;
+            }
+            HyperbolaShape::Curve => (
+                polylines_path(&hyperbola(hyperbolic.radius, &viewport.0)),
+                materials.circle.clone(),
+                2.,
+            ),
+            HyperbolaShape::Asymptotes => {
+                let dashes: Vec<_> = asymptotes(&viewport.0)
+                    .iter()
+                    .flat_map(|asymptote| asymptote.dashes(6., 4.))
+                    .collect();
+                (segments_path(&dashes), materials.axis.clone(), 1.)
+            }
+            HyperbolaShape::RadiusLine => (
+                segments_path(&[hyperbolic.radius_line]),
+                materials.radius.clone(),
+                1.,
+            ),
+        };
+        commands.insert(entity, stroke(&path, material, width));
+    }
+
+    for (entity, line) in lines.iter() {
+        let path = segments_path(&hyperbolic.segments(line.0, &viewport.0));
+        commands.insert(
+            entity,
+            stroke(&path, materials.function(line.0.circular()), 2.),
+        );
+    }
+}
+
+/// Hides whichever of the circle and the hyperbola isn't shown. This runs
+/// after `UPDATE`, where rebuilding a shape makes it visible again.
+pub fn view_system(
+    hyperbolic: Res<HyperbolicView>,
+    mut shapes: QuerySet<(
+        Query<&mut Visible, With<CircleOnly>>,
+        Query<&mut Visible, With<HyperbolaOnly>>,
+    )>,
+) {
+    for mut visible in shapes.q0_mut().iter_mut() {
+        if visible.is_visible == hyperbolic.0 {
+            visible.is_visible = !hyperbolic.0;
+        }
+    }
+    for mut visible in shapes.q1_mut().iter_mut() {
+        if visible.is_visible != hyperbolic.0 {
+            visible.is_visible = hyperbolic.0;
+        }
+    }
+}
+
+/// Shows the hyperbolic functions' values in the readout while the hyperbola
+/// is shown, next to the circular ones.
+pub fn hyperbolic_text_system(
+    hyperbolic: Res<HyperbolicView>,
+    geometry: Res<TrigGeometry>,
+    mut texts: Query<(&mut Text, &mut Style), With<HyperbolicText>>,
+) {
+    let (display, value) = if hyperbolic.0 {
+        let t = parameter(geometry.theta);
+        (
+            Display::Flex,
+            HyperbolicGeometry::new(t, geometry.radius).readout(),
+        )
+    } else {
+        (Display::None, String::new())
+    };
+    for (mut text, mut style) in texts.iter_mut() {
+        if style.display != display {
+            style.display = display;
+        }
+        if text.value != value {
+            text.value = value.clone();
+        }
+    }
+}
+
+/// Writes the value of every function into its row of the readout panel.
+pub fn readout_system(
+    geometry: Res<TrigGeometry>,
+    display: Res<ValueDisplay>,
+    mut texts: Query<(&FunctionText, &mut Text)>,
+) {
+    for (function, mut txt) in texts.iter_mut() {
+        let value = format!(
+            "{} θ = {:<12}",
+            function.0.name(),
+            format_value(function.0, geometry.theta, *display)
+        );
+        // only assign on change so the text isn't laid out again every frame
+        if txt.value != value {
+            txt.value = value;
+        }
+    }
+}
+
+/// Hides the lines, graphs and readout rows of the functions that are toggled
+/// off, and the lines while the hyperbola is shown.
+///
+/// Hidden lines are not kept up to date (rebuilding a shape makes it visible
+/// again), so a line is redrawn here when it is shown again.
+#[allow(clippy::too_many_arguments)]
+pub fn function_visibility_system(
+    commands: &mut Commands,
+    shown: Res<ShownFunctions>,
+    hyperbolic: Res<HyperbolicView>,
+    geometry: Res<TrigGeometry>,
+    viewport: Res<Viewport>,
+    materials: Res<LineMaterials>,
+    mut shapes: QuerySet<(
+        Query<(Entity, &FunctionLine, &mut Visible)>,
+        Query<(&WaveCurve, &mut Visible)>,
+        Query<(&WaveMarker, &mut Visible)>,
+        Query<(&WaveAsymptotes, &mut Visible)>,
+    )>,
+    mut texts: Query<(&FunctionText, &mut Style)>,
+) {
+    for (entity, line, mut visible) in shapes.q0_mut().iter_mut() {
+        let is_visible = shown.contains(line.0) && !hyperbolic.0;
+        if visible.is_visible != is_visible {
+            visible.is_visible = is_visible;
+            if is_visible {
+                let path = function_path(&geometry, line.0, &viewport.0);
+                commands.insert(
+                    entity,
+                    stroke(&path, materials.function(line.0), line_width(line.0)),
+                );
+            }
+        }
+    }
+
+    for (curve, mut visible) in shapes.q1_mut().iter_mut() {
+        let is_visible = shown.contains(curve.0);
+        if visible.is_visible != is_visible {
+            visible.is_visible = is_visible;
+        }
+    }
+
+    for (marker, mut visible) in shapes.q2_mut().iter_mut() {
+        let is_visible = shown.contains(marker.0);
+        if visible.is_visible != is_visible {
+            visible.is_visible = is_visible;
+        }
+    }
+
+    for (guides, mut visible) in shapes.q3_mut().iter_mut() {
+        let is_visible = shown.contains(guides.0);
+        if visible.is_visible != is_visible {
+            visible.is_visible = is_visible;
+        }
+    }
+
+    for (text, mut style) in texts.iter_mut() {
+        let display = if shown.contains(text.0) {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if style.display != display {
+            style.display = display;
+        }
+    }
+}
+
+/// Shows a ring around the point on the circle while θ sits on a snap angle.
+pub fn snap_cue_system(
+    geometry: Res<TrigGeometry>,
+    snapping: Res<Snapping>,
+    hyperbolic: Res<HyperbolicView>,
+    mut cue: Query<(&mut Transform, &mut Visible), With<SnapCue>>,
+) {
+    let is_visible = snapping.is_snapped(geometry.theta) && !hyperbolic.0;
+    let translation = to_vec2(geometry.point).extend(1.);
+    for (mut transform, mut visible) in cue.iter_mut() {
+        if visible.is_visible != is_visible {
+            visible.is_visible = is_visible;
+        }
+        if transform.translation != translation {
+            transform.translation = translation;
+        }
+    }
+}
+
+/// Redraws the graph's axis, curves and asymptotes when the layout changes.
+pub fn wave_graph_system(
+    commands: &mut Commands,
+    graph: ChangedRes<WaveGraph>,
+    materials: Res<LineMaterials>,
+    axis: Query<Entity, With<WaveAxis>>,
+    curves: Query<(Entity, &WaveCurve)>,
+    asymptotes: Query<(Entity, &WaveAsymptotes)>,
+) {
+    let height = WAVE_LIMIT * graph.amplitude;
+    for entity in axis.iter() {
+        commands.update_line(
+            entity,
+            materials.axis.clone(),
+            (
+                Vec2::new(graph.origin.x, graph.origin.y - height),
+                Vec2::new(graph.origin.x, graph.origin.y + height),
+            ),
+            1.,
+        );
+    }
+
+    for (entity, curve) in curves.iter() {
+        let path = polylines_path(&graph.curve(curve.0, WAVE_SAMPLES));
+        commands.insert(entity, stroke(&path, materials.function(curve.0), 2.));
+    }
+
+    for (entity, guides) in asymptotes.iter() {
+        let dashes: Vec<_> = graph
+            .asymptotes(guides.0)
+            .iter()
+            .flat_map(|guide| guide.dashes(6., 4.))
+            .collect();
+        commands.insert(
+            entity,
+            stroke(&segments_path(&dashes), materials.function(guides.0), 1.),
+        );
+    }
+}
+
+/// Moves the markers along the curves and redraws the connector from the circle.
+pub fn wave_marker_system(
+    commands: &mut Commands,
+    mut drawn: Local<Option<(TrigGeometry, WaveGraph)>>,
+    geometry: Res<TrigGeometry>,
+    graph: Res<WaveGraph>,
+    materials: Res<LineMaterials>,
+    mut markers: Query<(&WaveMarker, &mut Transform)>,
+    connector: Query<Entity, With<WaveConnector>>,
+) {
+    // the markers are only moved, not rebuilt. That's cheap enough to do every
+    // frame, which also covers the frame in which their mesh is first built
+    for (marker, mut transform) in markers.iter_mut() {
+        let translation = to_vec2(graph.point(marker.0, geometry.theta)).extend(1.);
+        if transform.translation != translation {
+            transform.translation = translation;
+        }
+    }
+
+    if *drawn == Some((*geometry, *graph)) {
+        return;
+    }
+    *drawn = Some((*geometry, *graph));
+
+    let dashes = graph.connector(&geometry).dashes(6., 4.);
+    for entity in connector.iter() {
+        commands.insert(
+            entity,
+            stroke(&segments_path(&dashes), materials.radius.clone(), 1.),
+        );
+    }
+}
+
+/// How close, in pixels, a click has to be to a point to pick it up.
+const GRAB_DISTANCE: f32 = 12.;
+
+#[derive(Default)]
+pub struct MouseState {
+    cursor_pos: Vec2,
+    dragging_graph: bool,
+    dragging_factor: bool,
+}
+
+/// Drags θ around the circle or along the wave graph. While an inverse
+/// function is picked, dragging on the circle moves its value along the guide
+/// instead and θ follows to the principal value. On the complex plane, the
+/// factor can be picked up and dragged too, and on the hyperbola the point
+/// follows the direction of the cursor.
+#[allow(clippy::too_many_arguments)]
+pub fn mouse_click_system(
+    mut state: Local<MouseState>,
+    mut set_theta: ResMut<Events<SetTheta>>,
+    mut inverse: ResMut<Inverse>,
+    mut complex: ResMut<ComplexPlane>,
+    hyperbolic: Res<HyperbolicView>,
+    radius: Res<Radius>,
+    paused_button_hovered: Res<PauseButtonHovered>,
+    unit: Res<AngleUnit>,
+    snapping: Res<Snapping>,
+    graph: Res<WaveGraph>,
+    windows: Res<Windows>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    camera: Query<&Transform, With<MainCamera>>,
+) {
+    let window = windows.get_primary().unwrap();
+    if let Some(pos) = window.cursor_position() {
+        // the camera is moved to make room for the graph, so its offset has to
+        // be added to get from window to world coordinates
+        let camera_offset = camera
+            .iter()
+            .next()
+            .map_or(Vec2::zero(), |transform| transform.translation.truncate());
+        state.cursor_pos = pos - Vec2::new(window.width() as f32 / 2., window.height() as f32 / 2.)
+            + camera_offset;
+    }
+
+    let cursor = Point::new(state.cursor_pos.x, state.cursor_pos.y);
+
+    // whatever was clicked keeps being dragged, even if the cursor wanders
+    // from the circle onto the graph or back
+    if mouse_button_input.just_pressed(MouseButton::Left) {
+        state.dragging_factor = complex.enabled
+            && complex.factor.map_or(false, |factor| {
+                let point = factor.to_point(radius.0);
+                Segment::new(point, cursor).length() <= GRAB_DISTANCE
+            });
+        state.dragging_graph = !state.dragging_factor && graph.contains_x(state.cursor_pos.x);
+    }
+
+    // the other buttons pan the view, see `camera::pan_system`
+    let panning = mouse_button_input.pressed(MouseButton::Right)
+        || mouse_button_input.pressed(MouseButton::Middle);
+    if mouse_button_input.pressed(MouseButton::Left) && !panning {
+        if !paused_button_hovered.0 {
+            if state.dragging_factor {
+                let factor = Some(Complex::from_point(cursor, radius.0));
+                if complex.factor != factor {
+                    complex.factor = factor;
+                }
+                return;
+            }
+
+            if let (Some(function), false) = (inverse.function, state.dragging_graph) {
+                let value = function.value_at(cursor, radius.0);
+                if inverse.value != value {
+                    inverse.value = value;
+                }
+                if let Some(theta) = function.principal(value) {
+                    set_theta.send(SetTheta {
+                        theta,
+                        source: ThetaSource::Mouse,
+                    });
+                }
+                return;
+            }
+
+            // θ is the hyperbolic angle, see `crate::hyperbolic`
+            if hyperbolic.0 && !state.dragging_graph {
+                set_theta.send(SetTheta {
+                    theta: theta_for(parameter_at(cursor)),
+                    source: ThetaSource::Mouse,
+                });
+                return;
+            }
+
+            let angle = if state.dragging_graph {
+                graph.theta(state.cursor_pos.x)
+            } else {
+                state.cursor_pos.y.atan2(state.cursor_pos.x)
+            };
+            let theta = match snapping.snap(angle) {
+                Some(snapped) if snapping.enabled => snapped,
+                _ => unit.snap(angle),
+            };
+            set_theta.send(SetTheta {
+                theta,
+                source: ThetaSource::Mouse,
+            });
+        }
+    }
+}
+
+/// Where the camera sits before any panning: far enough right that the
+/// origin, and with it the circle, ends up in the middle of the circle's panel.
+pub fn camera_home(window: &Window, placement: &Placement) -> Point {
+    Layout::placed(
+        window.width(),
+        window.height(),
+        &CameraView::default(),
+        placement,
+    )
+    .home
+}
+
+/// Lays out the circle on the left of the window and the wave graph on the
+/// right, then zooms and pans the whole scene.
+pub fn resize_circle_system(
+    mut radius: ResMut<Radius>,
+    mut graph: ResMut<WaveGraph>,
+    mut viewport: ResMut<Viewport>,
+    view: Res<CameraView>,
+    placement: Res<Placement>,
+    windows: Res<Windows>,
+    mut camera: Query<&mut Transform, With<MainCamera>>,
+) {
+    let window = windows.get_primary().unwrap();
+    let layout = Layout::placed(window.width(), window.height(), &view, &placement);
+    // only write on change, the circle is rebuilt whenever the radius is mutated
+    if radius.0 != layout.radius {
+        radius.0 = layout.radius;
+    }
+
+    // the circle stays at the origin, instead the camera is moved so that the
+    // origin ends up in the middle of the circle's panel
+    let camera_position = view.camera(layout.home);
+    for mut transform in camera.iter_mut() {
+        let translation = Vec3::new(
+            camera_position.x,
+            camera_position.y,
+            transform.translation.z,
+        );
+        if transform.translation != translation {
+            transform.translation = translation;
+        }
+    }
+
+    let new_viewport = Viewport(layout.viewport);
+    if *viewport != new_viewport {
+        *viewport = new_viewport;
+    }
+
+    let new_graph = WaveGraph {
+        periods: graph.periods,
+        ..layout.graph
+    };
+    if *graph != new_graph {
+        *graph = new_graph;
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{Point, Segment};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+
+pub fn to_vec2(point: Point) -> Vec2 {
+    Vec2::new(point.x, point.y)
+}
+
+pub fn to_line(segment: Segment) -> (Vec2, Vec2) {
+    (to_vec2(segment.start), to_vec2(segment.end))
+}
+
+pub fn stroke(shape: &impl Geometry, material: Handle<ColorMaterial>, width: f32) -> impl Bundle {
+    GeometryBuilder::build_as(
+        shape,
+        material,
+        TessellationMode::Stroke(StrokeOptions::default().with_line_width(width)),
+        Transform::default(),
+    )
+}
+
+/// Like [`stroke`], but drawn behind everything else, e.g. for the grid.
+pub fn stroke_behind(
+    shape: &impl Geometry,
+    material: Handle<ColorMaterial>,
+    width: f32,
+) -> impl Bundle {
+    GeometryBuilder::build_as(
+        shape,
+        material,
+        TessellationMode::Stroke(StrokeOptions::default().with_line_width(width)),
+        Transform::from_translation(Vec3::new(0., 0., -1.)),
+    )
+}
+
+pub fn fill(shape: &impl Geometry, material: Handle<ColorMaterial>) -> impl Bundle {
+    GeometryBuilder::build_as(
+        shape,
+        material,
+        TessellationMode::Fill(FillOptions::default()),
+        Transform::default(),
+    )
+}
+
+/// A single path made of several disconnected polylines.
+pub fn polylines_path(polylines: &[Vec<Point>]) -> Path {
+    let mut builder = PathBuilder::new();
+    for polyline in polylines {
+        if let Some((first, rest)) = polyline.split_first() {
+            builder.move_to(to_vec2(*first));
+            for point in rest {
+                builder.line_to(to_vec2(*point));
+            }
+        }
+    }
+    builder.build()
+}
+
+/// A closed polygon, e.g. to be filled.
+pub fn polygon_path(points: &[Point]) -> Path {
+    let mut builder = PathBuilder::new();
+    if let Some((first, rest)) = points.split_first() {
+        builder.move_to(to_vec2(*first));
+        for point in rest {
+            builder.line_to(to_vec2(*point));
+        }
+        builder.close();
+    }
+    builder.build()
+}
+
+/// A single path made of several disconnected segments, e.g. the dashes of a
+/// dashed line.
+pub fn segments_path(segments: &[Segment]) -> Path {
+    let mut builder = PathBuilder::new();
+    for segment in segments {
+        builder.move_to(to_vec2(segment.start));
+        builder.line_to(to_vec2(segment.end));
+    }
+    builder.build()
+}
+
+fn line_shape(material: Handle<ColorMaterial>, line: (Vec2, Vec2), width: f32) -> impl Bundle {
+    stroke(&shapes::Line(line.0, line.1), material, width)
+}
+
+pub trait SpawnLine {
+    fn spawn_line(
+        &mut self,
+        material: Handle<ColorMaterial>,
+        line: (Vec2, Vec2),
+        width: f32,
+    ) -> &mut Self;
+}
+
+impl SpawnLine for Commands {
+    fn spawn_line(
+        &mut self,
+        material: Handle<ColorMaterial>,
+        line: (Vec2, Vec2),
+        width: f32,
+    ) -> &mut Self {
+        self.spawn(line_shape(material, line, width))
+    }
+}
+
+/// Re-tessellates the line of an already spawned entity, keeping the entity
+/// and its material around instead of despawning it.
+pub trait UpdateLine {
+    fn update_line(
+        &mut self,
+        entity: Entity,
+        material: Handle<ColorMaterial>,
+        line: (Vec2, Vec2),
+        width: f32,
+    ) -> &mut Self;
+}
+
+impl UpdateLine for Commands {
+    fn update_line(
+        &mut self,
+        entity: Entity,
+        material: Handle<ColorMaterial>,
+        line: (Vec2, Vec2),
+        width: f32,
+    ) -> &mut Self {
+        self.insert(entity, line_shape(material, line, width))
+    }
+}
@@ -0,0 +1,383 @@
+//! Draws a [`Scene`] into an image on the CPU, for when there's no window or GPU.
+
+use crate::geometry::Point;
+use crate::palette::Rgba;
+use crate::scene::{circle_points, Anchor, Scene, Shape, TextItem};
+use ab_glyph::{point, Font, FontArc, PxScale, ScaleFont};
+use std::f32::consts::PI;
+use std::io::Write;
+
+/// Every pixel is sampled this many times in each direction, which smooths
+/// the edges of the shapes.
+pub const SUPERSAMPLING: usize = 3;
+
+/// An image being drawn into, at [`SUPERSAMPLING`] times its final size, in
+/// linear RGB.
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<[f32; 3]>,
+    /// The world point at the top left corner.
+    origin: Point,
+}
+
+impl Canvas {
+    fn new(scene: &Scene) -> Self {
+        let width = scene.width().round().max(1.) as usize * SUPERSAMPLING;
+        let height = scene.height().round().max(1.) as usize * SUPERSAMPLING;
+        let background = scene.background;
+        Self {
+            width,
+            height,
+            pixels: vec![[background.r, background.g, background.b]; width * height],
+            origin: Point::new(scene.view.min.x, scene.view.max.y),
+        }
+    }
+
+    /// Where a world point lands on the canvas, with y pointing down.
+    fn to_canvas(&self, p: Point) -> Point {
+        let scale = SUPERSAMPLING as f32;
+        Point::new((p.x - self.origin.x) * scale, (self.origin.y - p.y) * scale)
+    }
+
+    fn blend(&mut self, x: usize, y: usize, color: Rgba, coverage: f32) {
+        let alpha = (color.a * coverage).clamp(0., 1.);
+        let pixel = &mut self.pixels[y * self.width + x];
+        pixel[0] += (color.r - pixel[0]) * alpha;
+        pixel[1] += (color.g - pixel[1]) * alpha;
+        pixel[2] += (color.b - pixel[2]) * alpha;
+    }
+
+    /// Fills the area inside any of the polygons, given in world units, using
+    /// the nonzero rule. Areas where polygons overlap are only painted once,
+    /// so translucent shapes don't get darker where their pieces meet.
+    fn fill(&mut self, polygons: &[Vec<Point>], color: Rgba) {
+        let edges: Vec<(Point, Point)> = polygons
+            .iter()
+            .flat_map(|polygon| {
+                let points: Vec<_> = polygon.iter().map(|p| self.to_canvas(*p)).collect();
+                let count = points.len();
+                (0..count).map(move |i| (points[i], points[(i + 1) % count]))
+            })
+            // a point that isn't finite, e.g. from a NaN θ, has nowhere to go
+            .filter(|(a, b)| a.y != b.y && [a.x, a.y, b.x, b.y].iter().all(|c| c.is_finite()))
+            .collect();
+        if edges.is_empty() {
+            return;
+        }
+
+        let top = edges
+            .iter()
+            .map(|(a, b)| a.y.min(b.y))
+            .fold(f32::MAX, f32::min);
+        let bottom = edges
+            .iter()
+            .map(|(a, b)| a.y.max(b.y))
+            .fold(f32::MIN, f32::max);
+        let first_row = top.floor().max(0.) as usize;
+        let last_row = (bottom.ceil().max(0.) as usize).min(self.height);
+
+        let mut crossings: Vec<(f32, i32)> = Vec::new();
+        for row in first_row..last_row {
+            // sample each row through the middle of its pixels
+            let y = row as f32 + 0.5;
+            crossings.clear();
+            for (a, b) in &edges {
+                let (upper, lower, winding) = if a.y < b.y { (a, b, 1) } else { (b, a, -1) };
+                if y >= upper.y && y < lower.y {
+                    let t = (y - upper.y) / (lower.y - upper.y);
+                    let x = upper.x + t * (lower.x - upper.x);
+                    // huge coordinates can still overflow on the way
+                    if x.is_finite() {
+                        crossings.push((x, winding));
+                    }
+                }
+            }
+            crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+            let mut winding = 0;
+            for i in 0..crossings.len() {
+                winding += crossings[i].1;
+                if winding == 0 || i + 1 == crossings.len() {
+                    continue;
+                }
+                // the pixels whose middles lie between this crossing and the next
+                let start = (crossings[i].0 - 0.5).ceil().max(0.) as usize;
+                let end = ((crossings[i + 1].0 - 0.5).ceil().max(0.) as usize).min(self.width);
+                for x in start..end {
+                    self.blend(x, row, color, 1.);
+                }
+            }
+        }
+    }
+
+    /// Strokes the polylines, with round joins so that bends don't show gaps.
+    fn stroke(&mut self, polylines: &[Vec<Point>], color: Rgba, width: f32) {
+        let half = width / 2.;
+        let mut polygons = Vec::new();
+        for polyline in polylines {
+            for pair in polyline.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                let length = ((b.x - a.x).powi(2) + (b.y - a.y).powi(2)).sqrt();
+                if length == 0. {
+                    continue;
+                }
+                let normal = Point::new(-(b.y - a.y) / length * half, (b.x - a.x) / length * half);
+                // all the pieces wind the same way, so where they overlap
+                // the nonzero rule still counts them as inside
+                polygons.push(vec![
+                    Point::new(a.x - normal.x, a.y - normal.y),
+                    Point::new(b.x - normal.x, b.y - normal.y),
+                    Point::new(b.x + normal.x, b.y + normal.y),
+                    Point::new(a.x + normal.x, a.y + normal.y),
+                ]);
+            }
+            if width > 1. && polyline.len() > 2 {
+                // a closed polyline also bends where it starts
+                let first = if polyline.first() == polyline.last() {
+                    0
+                } else {
+                    1
+                };
+                polygons.extend(
+                    polyline[first..polyline.len() - 1]
+                        .iter()
+                        .map(|joint| disc(*joint, half)),
+                );
+            }
+        }
+        self.fill(&polygons, color);
+    }
+
+    fn text(&mut self, item: &TextItem, font: &FontArc) {
+        let scale = PxScale::from(item.size * SUPERSAMPLING as f32);
+        let scaled = font.as_scaled(scale);
+        let glyphs: Vec<_> = item.text.chars().map(|c| scaled.glyph_id(c)).collect();
+        let mut width = 0.;
+        for (i, glyph) in glyphs.iter().enumerate() {
+            if i > 0 {
+                width += scaled.kern(glyphs[i - 1], *glyph);
+            }
+            width += scaled.h_advance(*glyph);
+        }
+        let height = scaled.ascent() - scaled.descent();
+
+        let position = self.to_canvas(item.position);
+        let (left, top) = match item.anchor {
+            Anchor::Center => (position.x - width / 2., position.y - height / 2.),
+            Anchor::TopLeft => (position.x, position.y),
+        };
+        let baseline = top + scaled.ascent();
+
+        let mut x = left;
+        for (i, glyph) in glyphs.iter().enumerate() {
+            if i > 0 {
+                x += scaled.kern(glyphs[i - 1], *glyph);
+            }
+            let positioned = glyph.with_scale_and_position(scale, point(x, baseline));
+            x += scaled.h_advance(*glyph);
+            if let Some(outline) = font.outline_glyph(positioned) {
+                let bounds = outline.px_bounds();
+                outline.draw(|gx, gy, coverage| {
+                    let px = bounds.min.x as i64 + gx as i64;
+                    let py = bounds.min.y as i64 + gy as i64;
+                    if px >= 0
+                        && py >= 0
+                        && (px as usize) < self.width
+                        && (py as usize) < self.height
+                    {
+                        self.blend(px as usize, py as usize, item.color, coverage);
+                    }
+                });
+            }
+        }
+    }
+
+    /// Averages each block of samples into one 8-bit sRGB pixel.
+    fn resolve(&self) -> (u32, u32, Vec<u8>) {
+        let width = self.width / SUPERSAMPLING;
+        let height = self.height / SUPERSAMPLING;
+        let samples = (SUPERSAMPLING * SUPERSAMPLING) as f32;
+        let mut data = Vec::with_capacity(width * height * 3);
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = [0.; 3];
+                for sy in 0..SUPERSAMPLING {
+                    for sx in 0..SUPERSAMPLING {
+                        let pixel = self.pixels
+                            [(y * SUPERSAMPLING + sy) * self.width + x * SUPERSAMPLING + sx];
+                        for (total, c) in sum.iter_mut().zip(pixel.iter()) {
+                            *total += c;
+                        }
+                    }
+                }
+                let color = Rgba::linear(sum[0] / samples, sum[1] / samples, sum[2] / samples);
+                data.extend_from_slice(&color.to_srgb8()[..3]);
+            }
+        }
+        (width as u32, height as u32, data)
+    }
+}
+
+/// A counter-clockwise (on the canvas) polygon round a small circle.
+fn disc(centre: Point, radius: f32) -> Vec<Point> {
+    const SIDES: usize = 12;
+    (0..SIDES)
+        .map(|i| {
+            let angle = 2. * PI * i as f32 / SIDES as f32;
+            Point::new(
+                centre.x + radius * angle.cos(),
+                centre.y + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+/// Draws the scene, one pixel per world unit, and returns its width, height
+/// and 8-bit RGB pixels row by row from the top. The text is left out if
+/// there's no font to draw it with.
+pub fn render(scene: &Scene, font: Option<&FontArc>) -> (u32, u32, Vec<u8>) {
+    let mut canvas = Canvas::new(scene);
+    for shape in &scene.shapes {
+        match shape {
+            Shape::Stroke {
+                polylines,
+                color,
+                width,
+            } => canvas.stroke(polylines, *color, *width),
+            Shape::Fill { polygon, color } => canvas.fill(std::slice::from_ref(polygon), *color),
+            Shape::Circle {
+                centre,
+                radius,
+                color,
+                width,
+            } => {
+                let points = circle_points(*centre, *radius);
+                match width {
+                    Some(width) => canvas.stroke(&[points], *color, *width),
+                    None => canvas.fill(&[points], *color),
+                }
+            }
+        }
+    }
+    if let Some(font) = font {
+        for text in &scene.texts {
+            canvas.text(text, font);
+        }
+    }
+    canvas.resolve()
+}
+
+/// Draws the scene and encodes it as a PNG.
+pub fn write_png<W: Write>(
+    scene: &Scene,
+    font: Option<&FontArc>,
+    writer: W,
+) -> Result<(), png::EncodingError> {
+    let (width, height, data) = render(scene, font);
+    let mut encoder = png::Encoder::new(writer, width, height);
+    encoder.set_color(png::ColorType::RGB);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Bounds;
+    use crate::palette;
+
+    fn scene(shapes: Vec<Shape>) -> Scene {
+        Scene {
+            view: Bounds::centered(Point::ORIGIN, 20., 10.),
+            background: palette::DARK.background,
+            shapes,
+            texts: Vec::new(),
+        }
+    }
+
+    fn pixel(image: &(u32, u32, Vec<u8>), x: u32, y: u32) -> [u8; 3] {
+        let i = ((y * image.0 + x) * 3) as usize;
+        [image.2[i], image.2[i + 1], image.2[i + 2]]
+    }
+
+    #[test]
+    fn fills_whole_pixels_and_blends_edges() {
+        let white = Rgba::linear(1., 1., 1.);
+        // covers the middle of the image and half of the pixels at x = 0
+        let square = vec![
+            Point::new(-9.5, -3.),
+            Point::new(-9.5, 3.),
+            Point::new(0., 3.),
+            Point::new(0., -3.),
+        ];
+        let image = render(
+            &scene(vec![Shape::Fill {
+                polygon: square,
+                color: white,
+            }]),
+            None,
+        );
+        assert_eq!((image.0, image.1), (20, 10));
+        assert_eq!(pixel(&image, 5, 5), [255, 255, 255]);
+        assert_eq!(
+            pixel(&image, 15, 5),
+            palette::DARK.background.to_srgb8()[..3]
+        );
+        assert_eq!(
+            pixel(&image, 5, 0),
+            palette::DARK.background.to_srgb8()[..3]
+        );
+        let edge = pixel(&image, 0, 5)[0];
+        assert!(edge > 100 && edge < 255);
+    }
+
+    #[test]
+    fn overlapping_stroke_pieces_are_painted_once() {
+        let half_red = palette::COS.with_alpha(0.5);
+        let bent = scene(vec![Shape::Stroke {
+            polylines: vec![vec![
+                Point::new(-8., 0.),
+                Point::new(0., 0.),
+                Point::new(0., 4.),
+            ]],
+            color: half_red,
+            width: 2.,
+        }]);
+        let image = render(&bent, None);
+        // the joint and the straight part look the same
+        assert_eq!(pixel(&image, 9, 4), pixel(&image, 5, 5));
+    }
+
+    #[test]
+    fn skips_points_that_are_not_finite() {
+        let white = Rgba::linear(1., 1., 1.);
+        let broken = scene(vec![
+            Shape::Fill {
+                polygon: vec![
+                    Point::new(f32::NAN, -3.),
+                    Point::new(-5., 3.),
+                    Point::new(f32::INFINITY, 3.),
+                ],
+                color: white,
+            },
+            Shape::Stroke {
+                polylines: vec![vec![Point::new(0., 0.), Point::new(f32::NAN, 4.)]],
+                color: white,
+                width: 2.,
+            },
+        ]);
+        let image = render(&broken, None);
+        assert_eq!(
+            pixel(&image, 10, 5),
+            palette::DARK.background.to_srgb8()[..3]
+        );
+    }
+
+    #[test]
+    fn encodes_a_png() {
+        let mut bytes = Vec::new();
+        write_png(&scene(Vec::new()), None, &mut bytes).unwrap();
+        assert_eq!(&bytes[1..4], b"PNG");
+    }
+}
@@ -0,0 +1,123 @@
+use crate::exact::{exact_value, ExactValue, EXACT_TOLERANCE};
+use crate::function::Function;
+use crate::geometry::Values;
+
+/// Values bigger than this are shown heading off to infinity rather than as
+/// a number.
+pub const UNBOUNDED_VALUE: f32 = 1e3;
+
+/// How the value of a function is written in the readout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueDisplay {
+    Decimal,
+    /// Exact values at the special angles, decimals everywhere else.
+    Exact,
+    /// Exact values next to their decimals.
+    Both,
+}
+
+impl Default for ValueDisplay {
+    fn default() -> Self {
+        ValueDisplay::Both
+    }
+}
+
+impl ValueDisplay {
+    pub const ALL: [ValueDisplay; 3] = [
+        ValueDisplay::Decimal,
+        ValueDisplay::Exact,
+        ValueDisplay::Both,
+    ];
+
+    /// The mode after this one, wrapping around.
+    pub fn next(self) -> Self {
+        let index = ValueDisplay::ALL
+            .iter()
+            .position(|mode| *mode == self)
+            .unwrap();
+        ValueDisplay::ALL[(index + 1) % ValueDisplay::ALL.len()]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ValueDisplay::Decimal => "decimal",
+            ValueDisplay::Exact => "exact",
+            ValueDisplay::Both => "both",
+        }
+    }
+}
+
+/// The value of `function` at θ as it is shown in the readout.
+///
+/// On an asymptote this is "undefined", and just short of one it's "→ +∞" or
+/// "→ -∞" instead of a meaningless huge number.
+pub fn format_value(function: Function, theta: f32, display: ValueDisplay) -> String {
+    let values = Values::new(theta);
+    let value = function.value(&values);
+    let decimal = if !function.is_defined(&values) {
+        ExactValue::Undefined.to_string()
+    } else if value.abs() > UNBOUNDED_VALUE {
+        format!("→ {}∞", if value > 0. { "+" } else { "-" })
+    } else {
+        format!("{:.5}", value)
+    };
+    let exact = match display {
+        ValueDisplay::Decimal => None,
+        _ => exact_value(function, theta, EXACT_TOLERANCE),
+    };
+    match (display, exact) {
+        (_, None) => decimal,
+        (ValueDisplay::Both, Some(exact @ ExactValue::Value { .. })) if !exact.is_integer() => {
+            format!("{} = {}", exact, decimal)
+        }
+        (_, Some(exact)) => exact.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_6};
+
+    #[test]
+    fn formats_each_mode() {
+        let format = |display| format_value(Function::Cos, FRAC_PI_6, display);
+        assert_eq!(format(ValueDisplay::Decimal), "0.86603");
+        assert_eq!(format(ValueDisplay::Exact), "√3/2");
+        assert_eq!(format(ValueDisplay::Both), "√3/2 = 0.86603");
+    }
+
+    #[test]
+    fn whole_and_undefined_values_are_not_repeated() {
+        assert_eq!(
+            format_value(Function::Sin, FRAC_PI_2, ValueDisplay::Both),
+            "1"
+        );
+        assert_eq!(
+            format_value(Function::Tan, FRAC_PI_2, ValueDisplay::Both),
+            "undefined"
+        );
+    }
+
+    #[test]
+    fn asymptotes_are_undefined_or_unbounded() {
+        let tan = |theta| format_value(Function::Tan, theta, ValueDisplay::Decimal);
+        assert_eq!(tan(FRAC_PI_2), "undefined");
+        assert_eq!(tan(FRAC_PI_2 - 1e-5), "undefined");
+        assert_eq!(tan(FRAC_PI_2 - 5e-4), "→ +∞");
+        assert_eq!(tan(FRAC_PI_2 + 5e-4), "→ -∞");
+        assert_eq!(tan(FRAC_PI_2 - 0.1), "9.96665");
+        assert_eq!(
+            format_value(Function::Csc, 0., ValueDisplay::Decimal),
+            "undefined"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_decimals() {
+        assert_eq!(
+            format_value(Function::Sin, 0.5, ValueDisplay::Exact),
+            "0.47943"
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use crate::raster;
+use crate::scene::Diagram;
+use ab_glyph::FontArc;
+use std::f32::consts::PI;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// A sweep of θ recorded at a fixed timestep, so the same settings always
+/// give the same frames however long each one takes to draw.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Recording {
+    /// θ in the first frame, in radians.
+    pub from: f32,
+    /// θ the sweep heads for, reached as the recording loops back to the start.
+    pub to: f32,
+    pub seconds: f32,
+    pub fps: f32,
+    /// How many times a GIF plays over again, or `None` to loop forever.
+    pub loops: Option<u16>,
+}
+
+impl Default for Recording {
+    fn default() -> Self {
+        Self {
+            from: 0.,
+            to: 2. * PI,
+            seconds: 4.,
+            fps: 30.,
+            loops: None,
+        }
+    }
+}
+
+impl Recording {
+    /// The most frames a recording has, two minutes at 30 frames a second.
+    /// Every one of them is drawn on the CPU and, in a GIF, kept in one file.
+    pub const MAX_FRAMES: usize = 3_600;
+
+    /// How many frames the recording has, at least one and at most
+    /// [`Recording::MAX_FRAMES`].
+    pub fn frames(&self) -> usize {
+        let frames = (self.seconds * self.fps).round();
+        if frames.is_nan() {
+            return 1;
+        }
+        (frames.max(1.).min(Self::MAX_FRAMES as f32)) as usize
+    }
+
+    /// Whether the recording has a length and a frame rate and at most
+    /// [`Recording::MAX_FRAMES`] frames, or else an error saying why not.
+    pub fn check(&self) -> Result<(), String> {
+        if !(self.seconds > 0. && self.fps > 0.) {
+            return Err(format!(
+                "a recording can't be {} seconds at {} frames per second",
+                self.seconds, self.fps
+            ));
+        }
+        let frames = (self.seconds * self.fps).round();
+        if frames <= Self::MAX_FRAMES as f32 {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} seconds at {} frames per second is {} frames, more than the {} a recording \
+                 can have",
+                self.seconds,
+                self.fps,
+                frames,
+                Self::MAX_FRAMES
+            ))
+        }
+    }
+
+    /// θ in each frame. The last frame stops one step short of `to`, so a full
+    /// turn loops without showing the same angle twice.
+    pub fn thetas(&self) -> impl Iterator<Item = f32> {
+        let frames = self.frames();
+        let (from, to) = (self.from, self.to);
+        (0..frames).map(move |frame| from + (to - from) * frame as f32 / frames as f32)
+    }
+
+    /// How long each frame of a GIF shows, in the hundredths of a second GIFs
+    /// count in. Frame rates that don't divide 100 are rounded to the nearest.
+    pub fn frame_delay(&self) -> u16 {
+        (100. / self.fps).round().max(1.) as u16
+    }
+
+    /// Draws every frame of the sweep on the CPU into an animated GIF.
+    pub fn write_gif<W: Write>(
+        &self,
+        diagram: &Diagram,
+        font: Option<&FontArc>,
+        writer: W,
+    ) -> Result<(), gif::EncodingError> {
+        let mut frames = self
+            .thetas()
+            .map(|theta| {
+                let scene = Diagram {
+                    theta,
+                    ..diagram.clone()
+                }
+                .scene();
+                raster::render(&scene, font)
+            })
+            .peekable();
+        let (width, height) = match frames.peek() {
+            Some((width, height, _)) => (*width as u16, *height as u16),
+            None => return Ok(()),
+        };
+
+        let mut encoder = gif::Encoder::new(writer, width, height, &[])?;
+        encoder.set_repeat(match self.loops {
+            Some(loops) => gif::Repeat::Finite(loops),
+            None => gif::Repeat::Infinite,
+        })?;
+        for (_, _, pixels) in frames {
+            // the slowest, best quality quantizing would take minutes for a long sweep
+            let mut frame = gif::Frame::from_rgb_speed(width, height, &pixels, 10);
+            frame.delay = self.frame_delay();
+            encoder.write_frame(&frame)?;
+        }
+        Ok(())
+    }
+}
+
+/// Where frame `index` of a sequence goes, numbered after the file name given
+/// for the whole sequence, e.g. `out/sweep.png` becomes `out/sweep-0007.png`.
+pub fn frame_path(path: &Path, index: usize) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map_or_else(|| "frame".into(), |stem| stem.to_string_lossy());
+    let name = match path.extension() {
+        Some(extension) => format!("{}-{:04}.{}", stem, index, extension.to_string_lossy()),
+        None => format!("{}-{:04}", stem, index),
+    };
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_theta_at_a_fixed_rate() {
+        let recording = Recording {
+            from: 0.,
+            to: 1.,
+            seconds: 2.,
+            fps: 5.,
+            loops: None,
+        };
+        let thetas: Vec<_> = recording.thetas().collect();
+        assert_eq!(thetas.len(), 10);
+        assert_eq!(thetas[0], 0.);
+        assert_eq!(thetas[5], 0.5);
+        assert!(thetas[9] < 1.);
+        assert_eq!(recording.frame_delay(), 20);
+    }
+
+    #[test]
+    fn keeps_to_the_most_frames() {
+        let long = Recording {
+            seconds: 1e6,
+            fps: 1e6,
+            ..Recording::default()
+        };
+        let err = long.check().unwrap_err();
+        assert!(err.contains("more than the 3600"), "{}", err);
+        assert_eq!(long.frames(), Recording::MAX_FRAMES);
+        assert!(Recording::default().check().is_ok());
+        let still = Recording {
+            fps: 0.,
+            ..Recording::default()
+        };
+        assert!(still.check().is_err());
+        assert_eq!(still.frames(), 1);
+    }
+
+    #[test]
+    fn sweeps_backwards_too() {
+        let recording = Recording {
+            from: PI,
+            to: 0.,
+            ..Recording::default()
+        };
+        let thetas: Vec<_> = recording.thetas().collect();
+        assert_eq!(thetas.len(), 120);
+        assert!(thetas.windows(2).all(|pair| pair[1] < pair[0]));
+    }
+
+    #[test]
+    fn numbers_frames_after_the_file_name() {
+        assert_eq!(
+            frame_path(Path::new("out/sweep.png"), 7),
+            PathBuf::from("out/sweep-0007.png")
+        );
+        assert_eq!(
+            frame_path(Path::new("sweep"), 12),
+            PathBuf::from("sweep-0012")
+        );
+    }
+
+    #[test]
+    fn writes_a_gif_per_frame() {
+        let recording = Recording {
+            seconds: 0.5,
+            fps: 4.,
+            loops: Some(1),
+            ..Recording::default()
+        };
+        let diagram = Diagram {
+            width: 60.,
+            height: 40.,
+            ..Diagram::default()
+        };
+        let mut bytes = Vec::new();
+        recording.write_gif(&diagram, None, &mut bytes).unwrap();
+        let mut decoder = gif::DecodeOptions::new().read_info(&bytes[..]).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (60, 40));
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 25);
+            frames += 1;
+        }
+        assert_eq!(frames, 2);
+    }
+}
@@ -0,0 +1,723 @@
+//! The diagram as plain shapes and text, so it can be drawn without a window.
+
+use crate::angle::AngleUnit;
+use crate::annotation::{angle_arc, arc_label_position, place_labels, sector, SegmentLabel};
+use crate::complex::{axis_labels, roots_of_unity, ComplexPlane};
+use crate::function::Function;
+use crate::geometry::{Bounds, Point, Segment, TrigGeometry};
+use crate::grid::Grid;
+use crate::hyperbolic::{asymptotes, hyperbola, parameter, HyperbolicFunction, HyperbolicGeometry};
+use crate::inverse::InverseFunction;
+use crate::layout::Layout;
+use crate::palette::{self, Rgba, Theme};
+use crate::readout::{format_value, ValueDisplay};
+use crate::view::CameraView;
+use crate::wave::WAVE_LIMIT;
+use std::f32::consts::PI;
+
+/// The functions plotted on the wave graph.
+pub const WAVE_FUNCTIONS: [Function; 3] = [Function::Sin, Function::Cos, Function::Tan];
+pub const WAVE_SAMPLES: usize = 256;
+/// The length of the arrowheads on lines that run off screen.
+pub const ARROW_SIZE: f32 = 10.;
+/// The length of the minor grid ticks, the major ones are twice as long.
+pub const TICK_SIZE: f32 = 4.;
+/// The size of the labels drawn on the canvas, and roughly how wide each
+/// character of them is, as a share of that size.
+pub const LABEL_FONT_SIZE: f32 = 16.;
+pub const LABEL_CHAR_WIDTH: f32 = 0.6;
+pub const GRID_LABEL_FONT_SIZE: f32 = 14.;
+pub const READOUT_FONT_SIZE: f32 = 18.;
+/// The radius of the dots that mark θ on the wave graph.
+pub const MARKER_RADIUS: f32 = 4.;
+
+/// The radius of the angle arc for a circle of `radius`.
+pub fn arc_radius(radius: f32) -> f32 {
+    (radius * 0.2).min(40.)
+}
+
+pub fn line_width(function: Function) -> f32 {
+    // the archaic lines lie on top of the axes and the secant lines,
+    // so they are drawn thicker to stay visible underneath those
+    if function.is_archaic() {
+        4.
+    } else {
+        2.
+    }
+}
+
+/// What is drawn for `function`'s line: nothing while it's undefined, only the
+/// part within `viewport`, and an arrowhead where it runs off the edge.
+pub fn function_segments(
+    geometry: &TrigGeometry,
+    function: Function,
+    viewport: &Bounds,
+) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    if function.is_defined(&geometry.values) {
+        let segment = geometry.segment(function);
+        // leave room for the arrowhead to be seen in full
+        if let Some(clipped) = segment.clip(&viewport.shrink(ARROW_SIZE / 2.)) {
+            segments.push(clipped);
+            if clipped.end != segment.end {
+                segments.extend(clipped.arrowhead(ARROW_SIZE).iter());
+            }
+        }
+    }
+    segments
+}
+
+/// Where "θ" and the names of the functions go on the canvas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanvasLabels {
+    pub theta: Point,
+    /// Only the functions whose lines are on screen get a label.
+    pub functions: Vec<(Function, Point)>,
+}
+
+impl CanvasLabels {
+    /// Puts "θ" on the angle arc and the name of each of `shown` next to its
+    /// line, keeping the names clear of each other. Earlier functions get the
+    /// better spots.
+    pub fn new(geometry: &TrigGeometry, viewport: &Bounds, shown: &[Function]) -> Self {
+        let theta = arc_label_position(geometry.theta, arc_radius(geometry.radius) + 14.);
+        let theta_bounds = Bounds::centered(theta, LABEL_FONT_SIZE, LABEL_FONT_SIZE);
+
+        let mut placed: Vec<(Function, SegmentLabel)> = Vec::new();
+        for function in shown {
+            if !function.is_defined(&geometry.values) {
+                continue;
+            }
+            let segment = match geometry.segment(*function).clip(viewport) {
+                Some(segment) if segment.length() >= 1. => segment,
+                _ => continue,
+            };
+            placed.push((
+                *function,
+                SegmentLabel {
+                    segment,
+                    width: function_label(*function).chars().count() as f32
+                        * LABEL_CHAR_WIDTH
+                        * LABEL_FONT_SIZE,
+                    height: LABEL_FONT_SIZE,
+                },
+            ));
+        }
+        let requests: Vec<_> = placed.iter().map(|(_, label)| *label).collect();
+        let centres = place_labels(&requests, &[theta_bounds]);
+        Self {
+            theta,
+            functions: placed
+                .iter()
+                .map(|(function, _)| *function)
+                .zip(centres)
+                .collect(),
+        }
+    }
+}
+
+/// The text of a function's label on the canvas, e.g. "cos θ".
+pub fn function_label(function: Function) -> String {
+    format!("{} θ", function.name())
+}
+
+/// Which point of a piece of text its position refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    Center,
+    TopLeft,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// Polylines of the given width. A polyline whose ends meet is closed.
+    Stroke {
+        polylines: Vec<Vec<Point>>,
+        color: Rgba,
+        width: f32,
+    },
+    Fill {
+        polygon: Vec<Point>,
+        color: Rgba,
+    },
+    /// A circle, outlined with the given width, or filled if there's none.
+    Circle {
+        centre: Point,
+        radius: f32,
+        color: Rgba,
+        width: Option<f32>,
+    },
+}
+
+impl Shape {
+    fn segments(segments: &[Segment], color: Rgba, width: f32) -> Self {
+        Shape::Stroke {
+            polylines: segments
+                .iter()
+                .map(|segment| vec![segment.start, segment.end])
+                .collect(),
+            color,
+            width,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextItem {
+    pub position: Point,
+    pub text: String,
+    pub size: f32,
+    pub color: Rgba,
+    pub anchor: Anchor,
+}
+
+/// Everything on screen, in world units, in the order it is drawn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scene {
+    /// The part of the world in view, one world unit to a pixel. y points up.
+    pub view: Bounds,
+    pub background: Rgba,
+    pub shapes: Vec<Shape>,
+    /// Drawn on top of all the shapes.
+    pub texts: Vec<TextItem>,
+}
+
+impl Scene {
+    pub fn width(&self) -> f32 {
+        self.view.max.x - self.view.min.x
+    }
+
+    pub fn height(&self) -> f32 {
+        self.view.max.y - self.view.min.y
+    }
+}
+
+/// The settings that decide what the diagram looks like.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagram {
+    pub theta: f32,
+    /// The size of the window or image, in pixels.
+    pub width: f32,
+    pub height: f32,
+    pub view: CameraView,
+    /// The functions that are drawn, in order of priority for their labels.
+    pub shown: Vec<Function>,
+    pub grid: bool,
+    pub unit: AngleUnit,
+    pub display: ValueDisplay,
+    /// Whether to list the values in the top left corner, as the app does.
+    pub readout: bool,
+    pub theme: Theme,
+    /// The inverse function being shown and the value picked for it.
+    pub inverse: Option<(InverseFunction, f32)>,
+    /// Whether the circle is shown as the complex plane, and what's on it.
+    pub complex: ComplexPlane,
+    /// Whether the unit hyperbola is shown instead of the circle.
+    pub hyperbolic: bool,
+}
+
+impl Default for Diagram {
+    fn default() -> Self {
+        Self {
+            theta: 0.,
+            width: 1200.,
+            height: 720.,
+            view: CameraView::default(),
+            shown: Function::BASIC.to_vec(),
+            grid: true,
+            unit: AngleUnit::default(),
+            display: ValueDisplay::default(),
+            readout: true,
+            theme: Theme::default(),
+            inverse: None,
+            complex: ComplexPlane::default(),
+            hyperbolic: false,
+        }
+    }
+}
+
+impl Diagram {
+    /// Lays out the same diagram the app draws.
+    pub fn scene(&self) -> Scene {
+        let layout = Layout::new(self.width, self.height, &self.view);
+        let viewport = layout.viewport;
+        let geometry = TrigGeometry::new(self.theta, layout.radius);
+        let graph = layout.graph;
+        let colors = self.theme.palette();
+        let shown = |function: &Function| self.shown.contains(function);
+        let mut shapes = Vec::new();
+        let mut texts = Vec::new();
+
+        if self.grid {
+            let grid = Grid::new(layout.radius, &viewport, TICK_SIZE, self.unit);
+            shapes.push(Shape::segments(&grid.minor_lines, colors.grid_minor, 1.));
+            shapes.push(Shape::segments(&grid.major_lines, colors.grid_major, 1.));
+            shapes.push(Shape::segments(&grid.ticks, colors.axis, 1.));
+            texts.extend(grid.labels.into_iter().map(|label| TextItem {
+                position: label.position,
+                text: label.text,
+                size: GRID_LABEL_FONT_SIZE,
+                color: colors.grid_label,
+                anchor: Anchor::Center,
+            }));
+        }
+
+        let axes = [
+            Segment::new(
+                Point::new(viewport.min.x, 0.),
+                Point::new(viewport.max.x, 0.),
+            ),
+            Segment::new(
+                Point::new(0., viewport.min.y),
+                Point::new(0., viewport.max.y),
+            ),
+        ];
+        shapes.push(Shape::segments(&axes, colors.axis, 1.));
+        // the circle and the functions' lines on it make way for the hyperbola
+        if !self.hyperbolic {
+            shapes.push(Shape::Circle {
+                centre: Point::ORIGIN,
+                radius: layout.radius,
+                color: colors.circle,
+                width: Some(2.),
+            });
+            shapes.push(Shape::Fill {
+                polygon: angle_arc(geometry.theta, arc_radius(geometry.radius)),
+                color: colors.arc,
+            });
+            shapes.push(Shape::segments(&[geometry.radius_line], colors.radius, 1.));
+
+            // the same order the app spawns the lines in, so they overlap the same way
+            let order = [
+                Function::Sec,
+                Function::Csc,
+                Function::Cos,
+                Function::Sin,
+                Function::Tan,
+                Function::Cot,
+            ];
+            for function in order.iter().chain(Function::ARCHAIC.iter()) {
+                if shown(function) {
+                    shapes.push(Shape::segments(
+                        &function_segments(&geometry, *function, &viewport),
+                        palette::function_color(*function),
+                        line_width(*function),
+                    ));
+                }
+            }
+        }
+
+        let height = WAVE_LIMIT * graph.amplitude;
+        shapes.push(Shape::segments(
+            &[Segment::new(
+                Point::new(graph.origin.x, graph.origin.y - height),
+                Point::new(graph.origin.x, graph.origin.y + height),
+            )],
+            colors.axis,
+            1.,
+        ));
+        if !self.hyperbolic {
+            shapes.push(Shape::segments(
+                &graph.connector(&geometry).dashes(6., 4.),
+                colors.radius,
+                1.,
+            ));
+        }
+        for function in WAVE_FUNCTIONS.iter().filter(|function| shown(function)) {
+            let color = palette::function_color(*function);
+            shapes.push(Shape::Stroke {
+                polylines: graph.curve(*function, WAVE_SAMPLES),
+                color,
+                width: 2.,
+            });
+            shapes.push(Shape::Circle {
+                centre: graph.point(*function, geometry.theta),
+                radius: MARKER_RADIUS,
+                color,
+                width: None,
+            });
+            let dashes: Vec<_> = graph
+                .asymptotes(*function)
+                .iter()
+                .flat_map(|guide| guide.dashes(6., 4.))
+                .collect();
+            shapes.push(Shape::segments(&dashes, color, 1.));
+        }
+
+        if let Some((inverse, value)) = self.inverse {
+            let (start, end) = inverse.principal_range();
+            shapes.push(Shape::Fill {
+                polygon: sector(start, end, layout.radius),
+                color: colors.arc,
+            });
+            let guide: Vec<_> = inverse
+                .guide(layout.radius, &viewport)
+                .clip(&viewport)
+                .into_iter()
+                .collect();
+            shapes.push(Shape::segments(
+                &guide,
+                palette::function_color(inverse.function()),
+                3.,
+            ));
+            shapes.push(Shape::segments(
+                &inverse.angle_lines(value, layout.radius),
+                colors.highlight,
+                2.,
+            ));
+            shapes.push(Shape::Circle {
+                centre: inverse.handle(value, layout.radius),
+                radius: 6.,
+                color: colors.highlight,
+                width: None,
+            });
+        }
+
+        let hyperbolic = HyperbolicGeometry::new(parameter(geometry.theta), layout.radius);
+        if self.hyperbolic {
+            shapes.push(Shape::Fill {
+                polygon: hyperbolic.area(),
+                color: colors.arc,
+            });
+            let dashes: Vec<_> = asymptotes(&viewport)
+                .iter()
+                .flat_map(|asymptote| asymptote.dashes(6., 4.))
+                .collect();
+            shapes.push(Shape::segments(&dashes, colors.axis, 1.));
+            shapes.push(Shape::Stroke {
+                polylines: hyperbola(layout.radius, &viewport),
+                color: colors.circle,
+                width: 2.,
+            });
+            shapes.push(Shape::segments(
+                &[hyperbolic.radius_line],
+                colors.radius,
+                1.,
+            ));
+            for function in HyperbolicFunction::ALL.iter() {
+                shapes.push(Shape::segments(
+                    &hyperbolic.segments(*function, &viewport),
+                    palette::function_color(function.circular()),
+                    2.,
+                ));
+            }
+        }
+
+        if self.complex.enabled {
+            let radius = layout.radius;
+            if let (Some(factor), Some(product)) =
+                (self.complex.factor, self.complex.product(geometry.theta))
+            {
+                let mut lines = Segment::new(Point::ORIGIN, factor.to_point(radius)).dashes(6., 4.);
+                lines.push(Segment::new(Point::ORIGIN, product.to_point(radius)));
+                shapes.push(Shape::segments(&lines, colors.highlight, 2.));
+            }
+            if let Some(n) = self.complex.roots {
+                let mut corners: Vec<_> = roots_of_unity(n)
+                    .iter()
+                    .map(|root| root.to_point(radius))
+                    .collect();
+                corners.extend(corners.first().copied());
+                shapes.push(Shape::Stroke {
+                    polylines: vec![corners],
+                    color: colors.highlight,
+                    width: 2.,
+                });
+            }
+            for number in self
+                .complex
+                .factor
+                .iter()
+                .chain(self.complex.product(geometry.theta).iter())
+            {
+                shapes.push(Shape::Circle {
+                    centre: number.to_point(radius),
+                    radius: 6.,
+                    color: colors.highlight,
+                    width: None,
+                });
+            }
+            texts.extend(
+                axis_labels(radius, LABEL_FONT_SIZE)
+                    .iter()
+                    .map(|(name, position)| TextItem {
+                        position: *position,
+                        text: name.to_string(),
+                        size: LABEL_FONT_SIZE,
+                        color: colors.grid_label,
+                        anchor: Anchor::Center,
+                    }),
+            );
+        }
+
+        if !self.hyperbolic {
+            let labels = CanvasLabels::new(&geometry, &viewport, &self.shown);
+            texts.push(TextItem {
+                position: labels.theta,
+                text: "θ".into(),
+                size: LABEL_FONT_SIZE,
+                color: colors.highlight,
+                anchor: Anchor::Center,
+            });
+            for (function, position) in labels.functions {
+                texts.push(TextItem {
+                    position,
+                    text: function_label(function),
+                    size: LABEL_FONT_SIZE,
+                    color: palette::function_color(function),
+                    anchor: Anchor::Center,
+                });
+            }
+        }
+
+        if self.readout {
+            let mut lines = vec![(
+                format!("θ = {}", self.unit.format(geometry.theta)),
+                colors.text,
+            )];
+            if let Some((inverse, value)) = self.inverse {
+                lines.push((inverse.readout(value, self.unit), colors.highlight));
+            }
+            if self.complex.enabled {
+                let readout = self.complex.readout(geometry.theta, self.unit);
+                lines.extend(
+                    readout
+                        .lines()
+                        .map(|line| (line.to_string(), colors.highlight)),
+                );
+            }
+            if self.hyperbolic {
+                let readout = hyperbolic.readout();
+                lines.extend(
+                    readout
+                        .lines()
+                        .map(|line| (line.to_string(), colors.highlight)),
+                );
+            }
+            for function in Function::ALL.iter().filter(|function| shown(function)) {
+                lines.push((
+                    format!(
+                        "{} θ = {}",
+                        function.name(),
+                        format_value(*function, geometry.theta, self.display)
+                    ),
+                    palette::function_color(*function),
+                ));
+            }
+            for (i, (text, color)) in lines.into_iter().enumerate() {
+                texts.push(TextItem {
+                    position: Point::new(
+                        viewport.min.x + 20.,
+                        viewport.max.y - 20. - i as f32 * 1.25 * READOUT_FONT_SIZE,
+                    ),
+                    text,
+                    size: READOUT_FONT_SIZE,
+                    color,
+                    anchor: Anchor::TopLeft,
+                });
+            }
+        }
+
+        Scene {
+            view: viewport,
+            background: colors.background,
+            shapes,
+            texts,
+        }
+    }
+}
+
+/// A closed polyline round a circle, for renderers that can only draw lines.
+pub fn circle_points(centre: Point, radius: f32) -> Vec<Point> {
+    // enough points that the sides stay shorter than a few pixels
+    let samples = ((2. * PI * radius / 3.).ceil() as usize).clamp(16, 720);
+    (0..=samples)
+        .map(|i| {
+            let angle = 2. * PI * i as f32 / samples as f32;
+            Point::new(
+                centre.x + radius * angle.cos(),
+                centre.y + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    #[test]
+    fn lines_near_asymptotes_end_in_an_arrow() {
+        let viewport = Bounds::centered(Point::ORIGIN, 600., 400.);
+        let geometry = TrigGeometry::new(FRAC_PI_2 - 0.01, 100.);
+        let segments = function_segments(&geometry, Function::Tan, &viewport);
+        assert_eq!(segments.len(), 3);
+        assert!(segments[0].end.x <= 300.);
+
+        let geometry = TrigGeometry::new(FRAC_PI_2, 100.);
+        assert!(function_segments(&geometry, Function::Tan, &viewport).is_empty());
+
+        let geometry = TrigGeometry::new(FRAC_PI_4, 100.);
+        assert_eq!(
+            function_segments(&geometry, Function::Cos, &viewport),
+            vec![geometry.cos_line]
+        );
+    }
+
+    #[test]
+    fn every_shown_line_gets_a_label() {
+        let geometry = TrigGeometry::new(1., 200.);
+        let viewport = Bounds::centered(Point::ORIGIN, 600., 600.);
+        let labels = CanvasLabels::new(&geometry, &viewport, &Function::BASIC);
+        assert_eq!(labels.functions.len(), 6);
+        assert_eq!(labels.functions[0].0, Function::Cos);
+
+        // sin θ is zero, so its line is too short to label
+        let geometry = TrigGeometry::new(0.5, 200.);
+        let labels = CanvasLabels::new(&geometry, &viewport, &[Function::Sin, Function::Versin]);
+        assert_eq!(labels.functions.len(), 2);
+        let geometry = TrigGeometry::new(0., 200.);
+        let labels = CanvasLabels::new(&geometry, &viewport, &[Function::Sin]);
+        assert!(labels.functions.is_empty());
+    }
+
+    #[test]
+    fn diagram_covers_the_window() {
+        let diagram = Diagram {
+            theta: 0.785,
+            ..Diagram::default()
+        };
+        let scene = diagram.scene();
+        assert_eq!(scene.width(), 1200.);
+        assert_eq!(scene.height(), 720.);
+        assert!(scene
+            .texts
+            .iter()
+            .any(|text| text.text == "θ = 45.0°" && text.anchor == Anchor::TopLeft));
+        assert!(scene.texts.iter().any(|text| text.text == "tan θ"));
+    }
+
+    #[test]
+    fn hidden_functions_and_grid_are_left_out() {
+        let diagram = Diagram {
+            shown: vec![Function::Sin],
+            grid: false,
+            readout: false,
+            ..Diagram::default()
+        };
+        let scene = diagram.scene();
+        let colors: Vec<_> = scene
+            .shapes
+            .iter()
+            .map(|shape| match shape {
+                Shape::Stroke { color, .. }
+                | Shape::Fill { color, .. }
+                | Shape::Circle { color, .. } => *color,
+            })
+            .collect();
+        assert!(colors.contains(&palette::SIN));
+        assert!(!colors.contains(&palette::COS));
+        assert!(!colors.contains(&palette::DARK.grid_minor));
+        assert!(scene.texts.iter().all(|text| text.anchor == Anchor::Center));
+    }
+
+    #[test]
+    fn inverse_functions_are_drawn_with_their_angles() {
+        let diagram = Diagram {
+            inverse: Some((InverseFunction::Asin, 0.5)),
+            ..Diagram::default()
+        };
+        let scene = diagram.scene();
+        assert!(scene
+            .texts
+            .iter()
+            .any(|text| text.text == "asin 0.5 = 30.0°, also 150.0°"));
+        let radius = Layout::new(diagram.width, diagram.height, &diagram.view).radius;
+        let handle = Point::new(0., 0.5 * radius);
+        assert!(scene.shapes.iter().any(|shape| match shape {
+            Shape::Circle { centre, .. } => *centre == handle,
+            _ => false,
+        }));
+
+        let scene = Diagram::default().scene();
+        assert!(!scene.texts.iter().any(|text| text.text.starts_with("asin")));
+    }
+
+    #[test]
+    fn the_complex_plane_shows_its_factor_and_roots() {
+        let diagram = Diagram {
+            complex: ComplexPlane {
+                enabled: true,
+                factor: Some(ComplexPlane::DEFAULT_FACTOR),
+                roots: Some(5),
+            },
+            ..Diagram::default()
+        };
+        let scene = diagram.scene();
+        for text in ["Re", "Im", "z^5 = 1 at e^{2πik/5}, k = 0…4"].iter() {
+            assert!(scene.texts.iter().any(|item| item.text == *text));
+        }
+        assert!(scene
+            .texts
+            .iter()
+            .any(|item| item.text.starts_with("e^{iθ} = cos θ + i sin θ")));
+        // the factor and the product
+        let dots = scene
+            .shapes
+            .iter()
+            .filter(|shape| matches!(shape, Shape::Circle { radius, .. } if *radius == 6.))
+            .count();
+        assert_eq!(dots, 2);
+        // a pentagon, closed
+        assert!(scene.shapes.iter().any(|shape| match shape {
+            Shape::Stroke { polylines, .. } => {
+                polylines.len() == 1
+                    && polylines[0].len() == 6
+                    && polylines[0][0] == polylines[0][5]
+            }
+            _ => false,
+        }));
+    }
+
+    #[test]
+    fn the_hyperbola_replaces_the_circle() {
+        let diagram = Diagram {
+            theta: 1.,
+            hyperbolic: true,
+            ..Diagram::default()
+        };
+        let scene = diagram.scene();
+        let radius = Layout::new(diagram.width, diagram.height, &diagram.view).radius;
+        let hyperbolic = HyperbolicGeometry::new(parameter(1.), radius);
+        let lines = |color: Rgba| -> Vec<Vec<Point>> {
+            scene
+                .shapes
+                .iter()
+                .filter_map(|shape| match shape {
+                    Shape::Stroke {
+                        polylines,
+                        color: c,
+                        ..
+                    } if *c == color => Some(polylines.clone()),
+                    _ => None,
+                })
+                .flatten()
+                .collect()
+        };
+        // cosh and sinh are drawn in the colours of cos and sin
+        let cosh = hyperbolic.cosh_line;
+        let sinh = hyperbolic.sinh_line;
+        assert!(lines(palette::COS).contains(&vec![cosh.start, cosh.end]));
+        assert!(lines(palette::SIN).contains(&vec![sinh.start, sinh.end]));
+        assert!(!scene
+            .shapes
+            .iter()
+            .any(|shape| matches!(shape, Shape::Circle { width: Some(_), .. })));
+        assert!(scene
+            .texts
+            .iter()
+            .any(|text| text.text.starts_with("cosh t = ")));
+        assert!(!scene.texts.iter().any(|text| text.text == "θ"));
+    }
+}
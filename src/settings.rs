@@ -0,0 +1,165 @@
+use crate::angle::AngleUnit;
+use crate::config::Config;
+use crate::function::Function;
+use crate::palette::Theme;
+use serde::{Deserialize, Serialize};
+
+/// The choices a user makes in the app, kept from one launch to the next.
+///
+/// The only colours among them are the theme's. The functions keep their own
+/// colours in either theme, and there's no changing those, so they aren't
+/// saved.
+///
+/// Unlike a [`Config`], fields this version doesn't know are skipped, so that
+/// settings saved by a newer version still load.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// The functions that are shown, in the order of [`Function::ALL`].
+    pub functions: Vec<Function>,
+    pub theme: Theme,
+    pub unit: AngleUnit,
+    /// How fast θ turns, in radians per second.
+    pub speed: f32,
+    pub snapping: bool,
+    /// The size of the window when it isn't fullscreen, in logical pixels.
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::from(&Config::default())
+    }
+}
+
+impl From<&Config> for Settings {
+    fn from(config: &Config) -> Self {
+        Self {
+            functions: config.functions.clone(),
+            theme: config.theme,
+            unit: config.unit,
+            speed: config.speed,
+            snapping: config.snapping,
+            width: config.width,
+            height: config.height,
+        }
+    }
+}
+
+impl Settings {
+    /// The name of the file they're saved in, in the user's config directory.
+    pub const FILE_NAME: &'static str = "settings.ron";
+
+    pub fn from_ron(text: &str) -> Result<Self, ron::Error> {
+        ron::from_str(text)
+    }
+
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    /// These settings with whatever the user changed in the app, from
+    /// `started` to `current`, and the rest left as they are. That way
+    /// options given for a single launch, e.g. on the command line, are only
+    /// saved if the user goes on to change them.
+    pub fn with_changes(&self, started: &Settings, current: &Settings) -> Settings {
+        fn pick<T: Clone + PartialEq>(saved: &T, started: &T, current: &T) -> T {
+            if current != started {
+                current.clone()
+            } else {
+                saved.clone()
+            }
+        }
+        let (width, height) = pick(
+            &(self.width, self.height),
+            &(started.width, started.height),
+            &(current.width, current.height),
+        );
+        Settings {
+            functions: pick(&self.functions, &started.functions, &current.functions),
+            theme: pick(&self.theme, &started.theme, &current.theme),
+            unit: pick(&self.unit, &started.unit, &current.unit),
+            speed: pick(&self.speed, &started.speed, &current.speed),
+            snapping: pick(&self.snapping, &started.snapping, &current.snapping),
+            width,
+            height,
+        }
+    }
+
+    /// The config the app starts from with these settings, which the command
+    /// line can still override.
+    pub fn config(&self) -> Config {
+        Config {
+            functions: self.functions.clone(),
+            theme: self.theme,
+            unit: self.unit,
+            speed: self.speed,
+            snapping: self.snapping,
+            width: self.width,
+            height: self.height,
+            ..Config::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let settings = Settings {
+            functions: vec![Function::Sin, Function::Exsec],
+            theme: Theme::Light,
+            unit: AngleUnit::Turns,
+            speed: 1.5,
+            snapping: true,
+            width: 800.,
+            height: 600.,
+        };
+        let text = settings.to_ron().unwrap();
+        assert_eq!(Settings::from_ron(&text).unwrap(), settings);
+        assert_eq!(Settings::from(&settings.config()), settings);
+    }
+
+    #[test]
+    fn skips_what_it_doesnt_know() {
+        let settings = Settings::from_ron("(unit: gradians, colours: [])").unwrap();
+        assert_eq!(settings.unit, AngleUnit::Gradians);
+        assert_eq!(settings.functions, Settings::default().functions);
+    }
+
+    #[test]
+    fn only_takes_what_changed_in_the_app() {
+        let saved = Settings {
+            theme: Theme::Dark,
+            unit: AngleUnit::Radians,
+            ..Settings::default()
+        };
+        // launched with --theme light --functions sin
+        let started = Settings {
+            theme: Theme::Light,
+            functions: vec![Function::Sin],
+            ..saved.clone()
+        };
+        assert_eq!(saved.with_changes(&started, &started), saved);
+
+        let current = Settings {
+            unit: AngleUnit::Turns,
+            ..started.clone()
+        };
+        assert_eq!(
+            saved.with_changes(&started, &current),
+            Settings {
+                unit: AngleUnit::Turns,
+                ..saved
+            }
+        );
+    }
+
+    #[test]
+    fn starts_from_the_defaults() {
+        assert_eq!(Settings::default().config(), Config::default());
+    }
+}
@@ -0,0 +1,186 @@
+use crate::cli::Command;
+use ab_glyph::FontArc;
+use bevy::prelude::*;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use trig_geometry::plugin::{
+    GridVisible, HyperbolicView, ImageFormat, Inverse, ShownFunctions, SnapshotRequested,
+    TableRequested, Theta,
+};
+use trig_geometry::recording::frame_path;
+use trig_geometry::{
+    raster, svg, AngleUnit, CameraView, ComplexPlane, Config, Diagram, Recording, Table,
+    TableFormat, Theme, ValueDisplay,
+};
+
+/// Where snapshots and tables saved from the app are written.
+const SNAPSHOT_DIR: &str = "snapshots";
+
+/// Built in, so that snapshots can be taken from anywhere without the assets.
+fn font() -> FontArc {
+    FontArc::try_from_slice(include_bytes!("../assets/fonts/FiraMono-Regular.ttf"))
+        .expect("the bundled font is valid")
+}
+
+/// Draws the diagram on the CPU and saves it as a PNG, or writes it out as an SVG.
+pub fn write_snapshot(diagram: &Diagram, path: &Path, format: ImageFormat) -> io::Result<()> {
+    let file = BufWriter::new(File::create(path)?);
+    match format {
+        ImageFormat::Png => {
+            raster::write_png(&diagram.scene(), Some(&font()), file).map_err(encoding_error)
+        }
+        ImageFormat::Svg => svg::write_svg(&diagram.scene(), file),
+    }
+}
+
+fn encoding_error(err: impl std::error::Error + Send + Sync + 'static) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err)
+}
+
+/// Draws every frame of the sweep on the CPU, into a GIF or one PNG each.
+pub fn write_recording(
+    diagram: &Diagram,
+    recording: &Recording,
+    path: &Path,
+    gif: bool,
+) -> io::Result<()> {
+    let font = font();
+    if gif {
+        let file = BufWriter::new(File::create(path)?);
+        return recording
+            .write_gif(diagram, Some(&font), file)
+            .map_err(encoding_error);
+    }
+    for (index, theta) in recording.thetas().enumerate() {
+        let file = BufWriter::new(File::create(frame_path(path, index))?);
+        let frame = Diagram {
+            theta,
+            ..diagram.clone()
+        };
+        raster::write_png(&frame.scene(), Some(&font), file).map_err(encoding_error)?;
+    }
+    Ok(())
+}
+
+/// Carries out a command that doesn't need a window.
+pub fn run(command: Command, config: &Config) -> io::Result<()> {
+    let diagram = config.diagram();
+    let (path, result) = match command {
+        Command::Run => return Ok(()),
+        Command::Snapshot { path, format } => {
+            let result = write_snapshot(&diagram, &path, format);
+            (path, result)
+        }
+        Command::Record {
+            path,
+            gif,
+            recording,
+        } => {
+            let result = write_recording(&diagram, &recording, &path, gif);
+            (path, result)
+        }
+        Command::Table {
+            path,
+            format,
+            table,
+        } => {
+            let result =
+                File::create(&path).and_then(|file| table.write(format, BufWriter::new(file)));
+            (path, result)
+        }
+    };
+    result.map_err(|err| {
+        io::Error::new(
+            err.kind(),
+            format!("couldn't save {}: {}", path.display(), err),
+        )
+    })
+}
+
+/// Saves what's on screen when a snapshot is asked for.
+#[allow(clippy::too_many_arguments)]
+pub fn snapshot_system(
+    mut requested: ResMut<SnapshotRequested>,
+    theta: Res<Theta>,
+    view: Res<CameraView>,
+    shown: Res<ShownFunctions>,
+    grid_visible: Res<GridVisible>,
+    unit: Res<AngleUnit>,
+    display: Res<ValueDisplay>,
+    theme: Res<Theme>,
+    inverse: Res<Inverse>,
+    complex: Res<ComplexPlane>,
+    hyperbolic: Res<HyperbolicView>,
+    windows: Res<Windows>,
+) {
+    let format = match requested.0.take() {
+        Some(format) => format,
+        None => return,
+    };
+
+    let window = windows.get_primary().unwrap();
+    let diagram = Diagram {
+        theta: theta.0,
+        width: window.width(),
+        height: window.height(),
+        view: *view,
+        shown: shown.ordered(),
+        grid: grid_visible.0,
+        unit: *unit,
+        display: *display,
+        readout: true,
+        theme: *theme,
+        inverse: inverse.function.map(|function| (function, inverse.value)),
+        complex: *complex,
+        hyperbolic: hyperbolic.0,
+    };
+    let path = export_path("trig", format.extension());
+    report(
+        &path,
+        fs::create_dir_all(SNAPSHOT_DIR).and_then(|_| write_snapshot(&diagram, &path, format)),
+    );
+}
+
+/// Saves a table of the values of the shown functions over a full turn.
+pub fn table_system(
+    mut requested: ResMut<TableRequested>,
+    shown: Res<ShownFunctions>,
+    unit: Res<AngleUnit>,
+    display: Res<ValueDisplay>,
+) {
+    let format = match requested.0.take() {
+        Some(format) => format,
+        None => return,
+    };
+
+    let table = Table {
+        functions: shown.ordered(),
+        unit: *unit,
+        display: *display,
+        ..Table::default()
+    };
+    let path = export_path("trig-table", format.extension());
+    report(
+        &path,
+        fs::create_dir_all(SNAPSHOT_DIR)
+            .and_then(|_| File::create(&path))
+            .and_then(|file| table.write(format, BufWriter::new(file))),
+    );
+}
+
+/// A new file in [`SNAPSHOT_DIR`], named after the time it's saved.
+fn export_path(name: &str, extension: &str) -> PathBuf {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+    Path::new(SNAPSHOT_DIR).join(format!("{}-{}.{}", name, seconds, extension))
+}
+
+fn report(path: &Path, result: io::Result<()>) {
+    match result {
+        Ok(()) => println!("saved {}", path.display()),
+        Err(err) => eprintln!("couldn't save {}: {}", path.display(), err),
+    }
+}
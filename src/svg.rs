@@ -0,0 +1,266 @@
+//! Writes a [`Scene`] as an SVG, keeping the lines, circles and text as such so
+//! the drawing stays sharp when printed and can be edited afterwards.
+
+use crate::geometry::Point;
+use crate::palette::Rgba;
+use crate::scene::{Anchor, Scene, Shape, TextItem};
+use std::io::{self, Write};
+
+/// The font the labels ask for, with a fallback for viewers that lack it.
+const FONT_FAMILY: &str = "'Fira Mono', monospace";
+
+/// Maps world units to the SVG's, which have y pointing down.
+struct Transform {
+    left: f32,
+    top: f32,
+}
+
+impl Transform {
+    fn point(&self, p: Point) -> (String, String) {
+        (number(p.x - self.left), number(self.top - p.y))
+    }
+}
+
+/// Rounds to a hundredth of a pixel, and leaves out the trailing zeros.
+fn number(value: f32) -> String {
+    let text = format!("{:.2}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".into()
+    } else {
+        text.into()
+    }
+}
+
+fn hex(color: Rgba) -> String {
+    let [r, g, b, _] = color.to_srgb8();
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// The colour attribute, plus its opacity if it's see-through.
+fn paint(attribute: &str, color: Rgba) -> String {
+    if color.a < 1. {
+        format!(
+            r#"{}="{}" {}-opacity="{}""#,
+            attribute,
+            hex(color),
+            attribute,
+            number(color.a)
+        )
+    } else {
+        format!(r#"{}="{}""#, attribute, hex(color))
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn write_shape<W: Write>(out: &mut W, transform: &Transform, shape: &Shape) -> io::Result<()> {
+    match shape {
+        Shape::Stroke {
+            polylines,
+            color,
+            width,
+        } => {
+            if polylines.is_empty() {
+                return Ok(());
+            }
+            // one group per shape, so a whole layer can be restyled at once
+            let joins = r#"stroke-linecap="round" stroke-linejoin="round""#;
+            writeln!(
+                out,
+                r#"  <g fill="none" {} stroke-width="{}" {}>"#,
+                paint("stroke", *color),
+                number(*width),
+                joins
+            )?;
+            for polyline in polylines {
+                if let [start, end] = polyline.as_slice() {
+                    let (x1, y1) = transform.point(*start);
+                    let (x2, y2) = transform.point(*end);
+                    writeln!(
+                        out,
+                        r#"    <line x1="{}" y1="{}" x2="{}" y2="{}"/>"#,
+                        x1, y1, x2, y2
+                    )?;
+                } else {
+                    writeln!(
+                        out,
+                        r#"    <polyline points="{}"/>"#,
+                        points(transform, polyline)
+                    )?;
+                }
+            }
+            writeln!(out, "  </g>")
+        }
+        Shape::Fill { polygon, color } => writeln!(
+            out,
+            r#"  <polygon points="{}" {}/>"#,
+            points(transform, polygon),
+            paint("fill", *color)
+        ),
+        Shape::Circle {
+            centre,
+            radius,
+            color,
+            width,
+        } => {
+            let (cx, cy) = transform.point(*centre);
+            let style = match width {
+                Some(width) => format!(
+                    r#"fill="none" {} stroke-width="{}""#,
+                    paint("stroke", *color),
+                    number(*width)
+                ),
+                None => paint("fill", *color),
+            };
+            writeln!(
+                out,
+                r#"  <circle cx="{}" cy="{}" r="{}" {}/>"#,
+                cx,
+                cy,
+                number(*radius),
+                style
+            )
+        }
+    }
+}
+
+fn points(transform: &Transform, points: &[Point]) -> String {
+    points
+        .iter()
+        .map(|p| {
+            let (x, y) = transform.point(*p);
+            format!("{},{}", x, y)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn write_text<W: Write>(out: &mut W, transform: &Transform, text: &TextItem) -> io::Result<()> {
+    let (x, y) = transform.point(text.position);
+    let alignment = match text.anchor {
+        Anchor::Center => r#"text-anchor="middle" dominant-baseline="central""#,
+        Anchor::TopLeft => r#"text-anchor="start" dominant-baseline="hanging""#,
+    };
+    writeln!(
+        out,
+        r#"    <text x="{}" y="{}" font-size="{}" {} {}>{}</text>"#,
+        x,
+        y,
+        number(text.size),
+        paint("fill", text.color),
+        alignment,
+        escape(&text.text)
+    )
+}
+
+/// Writes the scene as an SVG document, one unit to a world unit.
+pub fn write_svg<W: Write>(scene: &Scene, mut out: W) -> io::Result<()> {
+    let transform = Transform {
+        left: scene.view.min.x,
+        top: scene.view.max.y,
+    };
+    let (width, height) = (number(scene.width()), number(scene.height()));
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+        width, height, width, height
+    )?;
+    writeln!(
+        out,
+        r#"  <rect width="{}" height="{}" {}/>"#,
+        width,
+        height,
+        paint("fill", scene.background)
+    )?;
+    for shape in &scene.shapes {
+        write_shape(&mut out, &transform, shape)?;
+    }
+    if !scene.texts.is_empty() {
+        writeln!(out, r#"  <g font-family="{}">"#, FONT_FAMILY)?;
+        for text in &scene.texts {
+            write_text(&mut out, &transform, text)?;
+        }
+        writeln!(out, "  </g>")?;
+    }
+    writeln!(out, "</svg>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Bounds;
+    use crate::palette;
+
+    fn svg(shapes: Vec<Shape>, texts: Vec<TextItem>) -> String {
+        let scene = Scene {
+            view: Bounds::centered(Point::ORIGIN, 200., 100.),
+            background: palette::DARK.background,
+            shapes,
+            texts,
+        };
+        let mut bytes = Vec::new();
+        write_svg(&scene, &mut bytes).unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn flips_the_y_axis() {
+        let out = svg(
+            vec![Shape::Stroke {
+                polylines: vec![vec![Point::new(0., 0.), Point::new(50., 25.5)]],
+                color: palette::COS,
+                width: 2.,
+            }],
+            Vec::new(),
+        );
+        assert!(out.contains(r#"viewBox="0 0 200 100""#));
+        assert!(out.contains(r#"<line x1="100" y1="50" x2="150" y2="24.5"/>"#));
+        assert!(out.contains(&format!(r#"stroke="{}""#, hex(palette::COS))));
+    }
+
+    #[test]
+    fn keeps_circles_and_translucency() {
+        let out = svg(
+            vec![
+                Shape::Circle {
+                    centre: Point::ORIGIN,
+                    radius: 40.,
+                    color: palette::DARK.circle,
+                    width: Some(2.),
+                },
+                Shape::Fill {
+                    polygon: vec![Point::ORIGIN, Point::new(10., 0.), Point::new(0., 10.)],
+                    color: palette::DARK.arc,
+                },
+            ],
+            Vec::new(),
+        );
+        assert!(out.contains(r#"<circle cx="100" cy="50" r="40" fill="none""#));
+        assert!(
+            out.contains(r##"points="100,50 110,50 100,40" fill="#f3f3f3" fill-opacity="0.15""##)
+        );
+    }
+
+    #[test]
+    fn escapes_text() {
+        let out = svg(
+            Vec::new(),
+            vec![TextItem {
+                position: Point::new(-90., 40.),
+                text: "θ < π & more".into(),
+                size: 18.,
+                color: palette::DARK.text,
+                anchor: Anchor::TopLeft,
+            }],
+        );
+        assert!(out.contains(r#"<text x="10" y="10" font-size="18""#));
+        assert!(out.contains(">θ &lt; π &amp; more</text>"));
+    }
+}
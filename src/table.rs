@@ -0,0 +1,281 @@
+use crate::angle::AngleUnit;
+use crate::function::Function;
+use crate::readout::{format_value, ValueDisplay};
+use std::f32::consts::PI;
+use std::io::{self, Write};
+
+/// Tables can be saved as comma separated values or as JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TableFormat {
+    Csv,
+    Json,
+}
+
+impl TableFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            TableFormat::Csv => "csv",
+            TableFormat::Json => "json",
+        }
+    }
+}
+
+/// The values of some functions at evenly spaced angles, written just as the
+/// readout shows them, so a saved table always agrees with the screen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    /// The first θ, in radians.
+    pub from: f32,
+    /// The last θ, in radians, if it falls on a step.
+    pub to: f32,
+    pub step: f32,
+    pub functions: Vec<Function>,
+    pub unit: AngleUnit,
+    pub display: ValueDisplay,
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Self {
+            from: 0.,
+            to: 2. * PI,
+            step: PI / 12.,
+            functions: Function::BASIC.to_vec(),
+            unit: AngleUnit::default(),
+            display: ValueDisplay::default(),
+        }
+    }
+}
+
+impl Table {
+    /// The most rows a table has, far more than anyone would read. A step
+    /// small enough to need more is more likely a typo.
+    pub const MAX_ROWS: usize = 100_000;
+
+    /// How many rows the table would have without `MAX_ROWS`, as a float
+    /// since it can be far too many for a `usize`.
+    fn rows(&self) -> f32 {
+        // a little slack so that `to` is included despite rounding
+        ((self.to - self.from).abs() / self.step + 1e-3).floor() + 1.
+    }
+
+    /// Whether the table has a step and at most [`Table::MAX_ROWS`] rows, or
+    /// else an error saying why not.
+    pub fn check(&self) -> Result<(), String> {
+        if self.step <= 0. || self.step.is_nan() {
+            return Err(format!(
+                "a table's step has to be more than 0, not {}",
+                self.step
+            ));
+        }
+        let rows = self.rows();
+        if rows <= Self::MAX_ROWS as f32 {
+            Ok(())
+        } else {
+            Err(format!(
+                "a step of {} from {} to {} makes {} rows, more than the {} a table can have",
+                self.step,
+                self.from,
+                self.to,
+                rows,
+                Self::MAX_ROWS
+            ))
+        }
+    }
+
+    /// Every θ in the table, up to [`Table::MAX_ROWS`] of them. Each is worked
+    /// out from the start rather than added up, so rounding errors don't build
+    /// up over a long table.
+    pub fn thetas(&self) -> Vec<f32> {
+        let rows = self.rows();
+        if self.step <= 0. || !rows.is_finite() {
+            return vec![self.from];
+        }
+        let span = self.to - self.from;
+        let rows = (rows as usize).min(Self::MAX_ROWS);
+        (0..rows)
+            .map(|i| self.from + span.signum() * self.step * i as f32)
+            .collect()
+    }
+
+    fn header(&self) -> Vec<String> {
+        let mut header = vec!["theta".to_string(), self.unit.name().to_string()];
+        header.extend(
+            self.functions
+                .iter()
+                .map(|function| function.name().to_string()),
+        );
+        header
+    }
+
+    fn row(&self, theta: f32) -> Vec<String> {
+        let mut row = vec![format!("{:.6}", theta), self.unit.format(theta)];
+        row.extend(
+            self.functions
+                .iter()
+                .map(|function| format_value(*function, theta, self.display)),
+        );
+        row
+    }
+
+    /// One row per θ, starting with θ in radians and in the table's unit.
+    pub fn write_csv<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "{}", csv_line(&self.header()))?;
+        for theta in self.thetas() {
+            writeln!(out, "{}", csv_line(&self.row(theta)))?;
+        }
+        Ok(())
+    }
+
+    /// An array with an object per θ. θ in radians is a number, everything
+    /// else is text as it's shown, since values can be "undefined" or exact.
+    pub fn write_json<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let header = self.header();
+        let thetas = self.thetas();
+        writeln!(out, "[")?;
+        for (i, theta) in thetas.iter().enumerate() {
+            let row = self.row(*theta);
+            let fields: Vec<String> = header
+                .iter()
+                .zip(row.iter())
+                .enumerate()
+                .map(|(column, (key, value))| {
+                    if column == 0 {
+                        format!("{}: {}", json_string(key), value)
+                    } else {
+                        format!("{}: {}", json_string(key), json_string(value))
+                    }
+                })
+                .collect();
+            let separator = if i + 1 < thetas.len() { "," } else { "" };
+            writeln!(out, "  {{{}}}{}", fields.join(", "), separator)?;
+        }
+        writeln!(out, "]")
+    }
+
+    pub fn write<W: Write>(&self, format: TableFormat, out: W) -> io::Result<()> {
+        match format {
+            TableFormat::Csv => self.write_csv(out),
+            TableFormat::Json => self.write_json(out),
+        }
+    }
+}
+
+fn csv_line(fields: &[String]) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            if field.contains(&[',', '"', '\n'][..]) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn json_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(table: &Table, format: TableFormat) -> String {
+        let mut bytes = Vec::new();
+        table.write(format, &mut bytes).unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn includes_both_ends() {
+        let thetas = Table::default().thetas();
+        assert_eq!(thetas.len(), 25);
+        assert!((thetas[24] - 2. * PI).abs() < 1e-5);
+
+        let backwards = Table {
+            from: PI,
+            to: 0.,
+            step: PI / 2.,
+            ..Table::default()
+        };
+        assert_eq!(backwards.thetas(), vec![PI, PI / 2., 0.]);
+    }
+
+    #[test]
+    fn keeps_to_the_most_rows() {
+        let tiny = Table {
+            step: 1e-9,
+            ..Table::default()
+        };
+        let err = tiny.check().unwrap_err();
+        assert!(err.contains("more than the 100000"), "{}", err);
+        assert_eq!(tiny.thetas().len(), Table::MAX_ROWS);
+        assert!(Table::default().check().is_ok());
+        let backwards = Table {
+            step: -1.,
+            ..Table::default()
+        };
+        assert!(backwards.check().is_err());
+        assert_eq!(backwards.thetas(), vec![0.]);
+    }
+
+    #[test]
+    fn matches_the_readout() {
+        let table = Table {
+            to: PI / 2.,
+            step: PI / 4.,
+            functions: vec![Function::Cos, Function::Tan],
+            display: ValueDisplay::Decimal,
+            ..Table::default()
+        };
+        let csv = text(&table, TableFormat::Csv);
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "theta,degrees,cos,tan");
+        assert_eq!(lines[2], "0.785398,45.0°,0.70711,1.00000");
+        assert_eq!(
+            lines[3],
+            format!(
+                "1.570796,90.0°,{},undefined",
+                format_value(Function::Cos, PI / 2., ValueDisplay::Decimal)
+            )
+        );
+    }
+
+    #[test]
+    fn writes_json() {
+        let table = Table {
+            to: 0.,
+            functions: vec![Function::Sin],
+            unit: AngleUnit::PiFraction,
+            ..Table::default()
+        };
+        assert_eq!(
+            text(&table, TableFormat::Json),
+            "[\n  {\"theta\": 0.000000, \"pi\": \"0\", \"sin\": \"0\"}\n]\n"
+        );
+        assert_eq!(json_string("a \"b\"\\"), "\"a \\\"b\\\"\\\\\"");
+    }
+
+    #[test]
+    fn quotes_csv_fields() {
+        assert_eq!(
+            csv_line(&["a".into(), "b,c".into(), "\"d\"".into()]),
+            "a,\"b,c\",\"\"\"d\"\"\""
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use crate::geometry::{Bounds, Point};
+
+/// How the user has zoomed and panned the scene.
+///
+/// Zooming scales the scene up about the centre of the circle rather than
+/// scaling the camera, so lines keep their width in pixels. Panning moves
+/// the camera away from its home, the spot that puts the circle in the middle
+/// of its panel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraView {
+    pub zoom: f32,
+    /// How far the camera has been moved from its home, in pixels.
+    pub pan: Point,
+}
+
+impl Default for CameraView {
+    fn default() -> Self {
+        Self {
+            zoom: 1.,
+            pan: Point::ORIGIN,
+        }
+    }
+}
+
+impl CameraView {
+    pub const MIN_ZOOM: f32 = 0.25;
+    pub const MAX_ZOOM: f32 = 50.;
+    /// How much one notch of the mouse wheel or one key press zooms by.
+    pub const ZOOM_STEP: f32 = 1.2;
+
+    /// Back to the whole circle in the middle of its panel.
+    pub fn fit(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Where the camera sits.
+    pub fn camera(&self, home: Point) -> Point {
+        Point::new(home.x + self.pan.x, home.y + self.pan.y)
+    }
+
+    /// Moves the scene along with a cursor that moved by `delta` pixels.
+    pub fn pan_by(&mut self, delta: Point) {
+        self.pan = Point::new(self.pan.x - delta.x, self.pan.y - delta.y);
+    }
+
+    /// Zooms by `factor`, keeping whatever is under `cursor` where it is.
+    /// `cursor` is in pixels from the centre of the window.
+    pub fn zoom_by(&mut self, factor: f32, home: Point, cursor: Point) {
+        let zoom = (self.zoom * factor).clamp(Self::MIN_ZOOM, Self::MAX_ZOOM);
+        let factor = zoom / self.zoom;
+        let camera = self.camera(home);
+        // the point under the cursor ends up `factor` times as far from the
+        // origin, so the camera has to follow it there
+        let under_cursor = Point::new(camera.x + cursor.x, camera.y + cursor.y);
+        self.pan = Point::new(
+            factor * under_cursor.x - cursor.x - home.x,
+            factor * under_cursor.y - cursor.y - home.y,
+        );
+        self.zoom = zoom;
+    }
+
+    /// The part of the scene a `width` by `height` window shows.
+    pub fn visible(&self, home: Point, width: f32, height: f32) -> Bounds {
+        Bounds::centered(self.camera(home), width, height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-3, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn zooming_keeps_the_cursor_still() {
+        let home = Point::new(240., 0.);
+        let cursor = Point::new(-100., 50.);
+        let mut view = CameraView::default();
+        view.pan_by(Point::new(30., -20.));
+
+        let camera = view.camera(home);
+        let before = Point::new(camera.x + cursor.x, camera.y + cursor.y);
+        view.zoom_by(2., home, cursor);
+        let camera = view.camera(home);
+        assert_close(camera.x + cursor.x, 2. * before.x);
+        assert_close(camera.y + cursor.y, 2. * before.y);
+    }
+
+    #[test]
+    fn zoom_is_clamped() {
+        let mut view = CameraView::default();
+        for _ in 0..100 {
+            view.zoom_by(CameraView::ZOOM_STEP, Point::ORIGIN, Point::ORIGIN);
+        }
+        assert_eq!(view.zoom, CameraView::MAX_ZOOM);
+        view.fit();
+        assert_eq!(view, CameraView::default());
+    }
+
+    #[test]
+    fn panning_follows_the_cursor() {
+        let mut view = CameraView::default();
+        view.pan_by(Point::new(10., 5.));
+        let visible = view.visible(Point::ORIGIN, 100., 100.);
+        assert_eq!(visible.min, Point::new(-60., -55.));
+    }
+}
@@ -0,0 +1,203 @@
+use crate::function::Function;
+use crate::geometry::{Point, Segment, TrigGeometry, Values};
+use std::f32::consts::PI;
+
+/// How far the curves are drawn above and below the axis before they are cut
+/// off, in units of the amplitude. Only tan ever gets that far.
+pub const WAVE_LIMIT: f32 = 2.;
+
+/// The graph of the functions against θ, "unrolled" from the circle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaveGraph {
+    /// Where θ = 0 sits on the graph's axis.
+    pub origin: Point,
+    /// The width of the plotted periods in world units.
+    pub width: f32,
+    /// World units per unit of function value. This is the circle's radius so
+    /// the graph lines up with the circle.
+    pub amplitude: f32,
+    /// How many 2π periods are plotted.
+    pub periods: f32,
+}
+
+impl Default for WaveGraph {
+    fn default() -> Self {
+        Self {
+            origin: Point::ORIGIN,
+            width: 0.,
+            amplitude: 0.,
+            periods: 1.,
+        }
+    }
+}
+
+impl WaveGraph {
+    /// The range of θ covered by the graph.
+    pub fn span(&self) -> f32 {
+        2. * PI * self.periods
+    }
+
+    /// The x coordinate of θ, wrapped into the plotted range.
+    pub fn x(&self, theta: f32) -> f32 {
+        self.origin.x + theta.rem_euclid(self.span()) / self.span() * self.width
+    }
+
+    /// The θ plotted at `x`, the inverse of [`WaveGraph::x`].
+    pub fn theta(&self, x: f32) -> f32 {
+        (x - self.origin.x) / self.width * self.span()
+    }
+
+    pub fn contains_x(&self, x: f32) -> bool {
+        x >= self.origin.x && x <= self.origin.x + self.width
+    }
+
+    /// The point of `function`'s curve at θ, kept within the drawn limits.
+    pub fn point(&self, function: Function, theta: f32) -> Point {
+        let limit = WAVE_LIMIT * self.amplitude;
+        let y = self.amplitude * function.value(&Values::new(theta));
+        Point::new(self.x(theta), self.origin.y + y.clamp(-limit, limit))
+    }
+
+    /// Samples `function` over the plotted range.
+    ///
+    /// The curve is split into several polylines wherever it leaves the drawn
+    /// limits, so the asymptotes of tan aren't joined up.
+    pub fn curve(&self, function: Function, samples: usize) -> Vec<Vec<Point>> {
+        let limit = WAVE_LIMIT * self.amplitude;
+        let inside = |point: Point| (point.y - self.origin.y).abs() <= limit;
+        // the point where the line from `from` (inside) to `to` (outside) leaves the limits
+        let clip = |from: Point, to: Point| {
+            let bound = self.origin.y + limit.copysign(to.y - self.origin.y);
+            let t = (bound - from.y) / (to.y - from.y);
+            let t = if t.is_finite() { t.clamp(0., 1.) } else { 0. };
+            Segment::new(from, to).lerp(t)
+        };
+
+        let mut curves = Vec::new();
+        let mut current = Vec::new();
+        let mut previous: Option<Point> = None;
+        for i in 0..=samples {
+            let t = i as f32 / samples as f32;
+            let theta = t * self.span();
+            let point = Point::new(
+                self.origin.x + t * self.width,
+                self.origin.y + self.amplitude * function.value(&Values::new(theta)),
+            );
+            if let Some(previous) = previous {
+                match (inside(previous), inside(point)) {
+                    (true, false) => {
+                        current.push(clip(previous, point));
+                        curves.push(std::mem::take(&mut current));
+                    }
+                    (false, true) => current.push(clip(point, previous)),
+                    _ => {}
+                }
+            }
+            if inside(point) {
+                current.push(point);
+            }
+            previous = Some(point);
+        }
+        curves.push(current);
+        curves.retain(|curve| curve.len() > 1);
+        curves
+    }
+
+    /// Vertical guides through the asymptotes of `function`, as tall as the
+    /// drawn limits.
+    pub fn asymptotes(&self, function: Function) -> Vec<Segment> {
+        let limit = WAVE_LIMIT * self.amplitude;
+        let mut guides = Vec::new();
+        for period in 0..self.periods.ceil() as usize {
+            for asymptote in function.asymptotes() {
+                let theta = asymptote + 2. * PI * period as f32;
+                // skip the right-hand edge of one period when the next one
+                // starts at the same place
+                if theta > self.span() || (period > 0 && *asymptote == 0.) {
+                    continue;
+                }
+                let x = self.origin.x + theta / self.span() * self.width;
+                guides.push(Segment::new(
+                    Point::new(x, self.origin.y - limit),
+                    Point::new(x, self.origin.y + limit),
+                ));
+            }
+        }
+        guides
+    }
+
+    /// Runs from the point on the circle to the same height on the sine curve.
+    pub fn connector(&self, geometry: &TrigGeometry) -> Segment {
+        Segment::new(geometry.point, self.point(Function::Sin, geometry.theta))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> WaveGraph {
+        WaveGraph {
+            origin: Point::new(300., 0.),
+            width: 600.,
+            amplitude: 100.,
+            periods: 1.,
+        }
+    }
+
+    #[test]
+    fn x_wraps_theta_into_the_graph() {
+        let graph = graph();
+        assert_eq!(graph.x(0.), 300.);
+        assert!((graph.x(PI) - 600.).abs() < 1e-3);
+        assert!((graph.x(-PI / 2.) - 750.).abs() < 1e-3);
+        assert!((graph.theta(graph.x(1.)) - 1.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn sin_is_one_unbroken_curve() {
+        let curves = graph().curve(Function::Sin, 64);
+        assert_eq!(curves.len(), 1);
+        assert_eq!(curves[0].len(), 65);
+    }
+
+    #[test]
+    fn tan_is_split_at_its_asymptotes() {
+        let graph = graph();
+        let curves = graph.curve(Function::Tan, 256);
+        assert_eq!(curves.len(), 3);
+        for point in curves.iter().flatten() {
+            assert!(point.y.abs() <= WAVE_LIMIT * graph.amplitude + 1e-3);
+        }
+    }
+
+    #[test]
+    fn asymptote_guides_line_up_with_the_gaps() {
+        let graph = graph();
+        let guides = graph.asymptotes(Function::Tan);
+        assert_eq!(guides.len(), 2);
+        assert!((guides[0].start.x - 450.).abs() < 1e-3);
+        assert!((guides[1].start.x - 750.).abs() < 1e-3);
+        assert_eq!(guides[0].length(), 2. * WAVE_LIMIT * graph.amplitude);
+
+        // cot is undefined at both ends of the graph and in the middle
+        assert_eq!(graph.asymptotes(Function::Cot).len(), 3);
+        assert!(graph.asymptotes(Function::Sin).is_empty());
+
+        let graph = WaveGraph {
+            periods: 2.,
+            ..graph
+        };
+        assert_eq!(graph.asymptotes(Function::Cot).len(), 5);
+    }
+
+    #[test]
+    fn connector_is_level_with_the_circle_point() {
+        let graph = graph();
+        let geometry = TrigGeometry::new(1., 100.);
+        let connector = graph.connector(&geometry);
+        assert_eq!(connector.start, geometry.point);
+        assert!((connector.end.y - geometry.point.y).abs() < 1e-3);
+        assert_eq!(connector.end.x, graph.x(1.));
+    }
+}
@@ -1,32 +1,15 @@
-#[cfg(target_arch = "wasm32")]
+//! Keeps the window the size of the browser's on the web.
+
+use bevy::prelude::*;
+use bevy::window::WindowId;
+use bevy::winit::WinitWindows;
 use crossbeam_channel::{unbounded, Receiver};
-#[cfg(target_arch = "wasm32")]
 use winit::dpi::LogicalSize;
 
-pub struct CosLine;
-pub struct SinLine;
-pub struct TanLine;
-pub struct CotLine;
-pub struct RadiusLine;
-pub struct Circle;
-
-pub struct CosText;
-pub struct SinText;
-pub struct TanText;
-pub struct CotText;
-pub struct ThetaText;
-
-pub struct Theta(pub f32);
-pub struct Radius(pub f32);
-pub struct Paused(pub bool);
-pub struct PauseButtonHovered(pub bool);
-
-#[cfg(target_arch = "wasm32")]
 pub struct WinitWebResizing {
     pub rx: Receiver<LogicalSize<f32>>,
 }
 
-#[cfg(target_arch = "wasm32")]
 impl WinitWebResizing {
     pub fn new() -> Self {
         use bevy::log;
@@ -58,3 +41,10 @@ impl WinitWebResizing {
         return Self { rx };
     }
 }
+
+pub fn web_resize_system(winit_windows: Res<WinitWindows>, web_resizing: Res<WinitWebResizing>) {
+    let winit_window = winit_windows.get_window(WindowId::primary()).unwrap();
+    for size in web_resizing.rx.clone().try_iter().last() {
+        winit_window.set_inner_size(size);
+    }
+}